@@ -1,22 +1,24 @@
-use crate::utils::read_as_columns::read_as_columns;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::Path;
 
-pub fn run_a() -> std::io::Result<()> {
-    // get the two columns as lists of integers
-    let columns = read_as_columns("src/day_1/input.txt")?;
-    let column1 = columns[0].iter().map(|s| s.parse::<i32>().unwrap()).collect::<Vec<i32>>();
-    let column2 = columns[1].iter().map(|s| s.parse::<i32>().unwrap()).collect::<Vec<i32>>();
+use crate::utils::input::input_path;
+use crate::utils::read_as_columns::{read_as_typed_columns, ColumnOptions, RaggedRows};
+use crate::utils::stream_lines::open_buffered;
 
-    // sort the columns
-    let mut sorted_column1 = column1.clone();
-    sorted_column1.sort();
-    let mut sorted_column2 = column2.clone();
-    sorted_column2.sort();
+fn read_lists() -> std::io::Result<(Vec<i64>, Vec<i64>)> {
+    let columns = read_as_typed_columns::<_, i64>(input_path("src/day_1/input.txt"), &ColumnOptions::default())?;
+    let [column1, column2]: [Vec<i64>; 2] = columns.try_into().map_err(|columns: Vec<Vec<i64>>| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, format!("expected 2 columns, found {}", columns.len()))
+    })?;
+    Ok((column1, column2))
+}
 
-    // find the absolute value difference between the two columns at each index
-    let mut total_distance = 0;
-    for i in 0..sorted_column1.len() {
-        total_distance += (sorted_column1[i] - sorted_column2[i]).abs();
-    }
+pub fn run_a() -> std::io::Result<()> {
+    let (column1, column2) = read_lists()?;
+
+    let total_distance = total_distance(column1, column2);
 
     // print the total distance
     println!("Total distance: {}", total_distance);
@@ -25,19 +27,222 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let columns = read_as_columns("src/day_1/input.txt")?;
-    let column1 = columns[0].iter().map(|s| s.parse::<i32>().unwrap()).collect::<Vec<i32>>();
-    let column2 = columns[1].iter().map(|s| s.parse::<i32>().unwrap()).collect::<Vec<i32>>();
+    let (column1, column2) = read_lists()?;
 
-    // for each number in column1, find how many times that number appears in column2. this becomes the 'similarity score' for that number and it is multipled by the number itself
-    let mut total_similarity_score = 0;
-    for num in column1 {
-        let similarity_score = column2.iter().filter(|&n| *n == num).count();
-        total_similarity_score += similarity_score as i32 * num;
-    }
+    let total_similarity_score = similarity_score(&column1, &column2);
 
     // print the total similarity score
     println!("Total similarity score: {}", total_similarity_score);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// pairs the smallest with the smallest, the second smallest with the second smallest and so on
+fn total_distance(mut column1: Vec<i64>, mut column2: Vec<i64>) -> i64 {
+    column1.sort_unstable();
+    column2.sort_unstable();
+
+    column1.iter().zip(&column2).map(|(a, b)| (a - b).abs()).sum()
+}
+
+// every number in column1 multiplied by how many times it appears in column2, counting column2 once up front
+fn similarity_score(column1: &[i64], column2: &[i64]) -> i64 {
+    let mut histogram: HashMap<i64, i64> = HashMap::with_capacity(column2.len());
+    for &num in column2 {
+        *histogram.entry(num).or_insert(0) += 1;
+    }
+
+    column1.iter().map(|num| num * histogram.get(num).copied().unwrap_or(0)).sum()
+}
+
+// Comparing more than two lists, e.g. ID exports from different systems. Lists can be different lengths, with
+// RaggedRows::Pad the missing cells at the bottom of the shorter ones are just left empty
+pub fn reconcile(path: &Path, delimiter: Option<&str>, header: bool, ragged: RaggedRows) -> std::io::Result<String> {
+    let options = ColumnOptions {
+        delimiter,
+        skip_header: if header { 1 } else { 0 },
+        ragged,
+    };
+    let columns = read_as_typed_columns::<_, String>(path, &options)?;
+
+    let mut lists = Vec::with_capacity(columns.len());
+    for column in &columns {
+        let list = column
+            .iter()
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value.parse::<i64>().map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?} is not a number", value))
+                })
+            })
+            .collect::<std::io::Result<Vec<i64>>>()?;
+        lists.push(list);
+    }
+
+    let mut names: Vec<String> = (1..=lists.len()).map(|i| format!("list {}", i)).collect();
+    if header {
+        if let Some(line) = open_buffered(path)?.lines().next() {
+            let line = line?;
+            let header_names: Vec<&str> = match delimiter {
+                Some(delimiter) => line.split(delimiter).map(|name| name.trim()).collect(),
+                None => line.split_whitespace().collect(),
+            };
+            for (name, header_name) in names.iter_mut().zip(header_names) {
+                *name = header_name.to_string();
+            }
+        }
+    }
+
+    Ok(reconcile_lists(&names, &lists))
+}
+
+// the report for lists that have been read and named
+fn reconcile_lists(names: &[String], lists: &[Vec<i64>]) -> String {
+    let mut report = String::new();
+    for (name, list) in names.iter().zip(lists) {
+        report.push_str(&format!("{}: {} values\n", name, list.len()));
+    }
+
+    // lists of different lengths are only compared as far as the shorter one goes
+    report.push_str("\nTotal distance\n");
+    report.push_str(&format_matrix(names, |i, j| total_distance(lists[i].clone(), lists[j].clone())));
+    report.push_str("\nSimilarity score (row values counted in the column)\n");
+    report.push_str(&format_matrix(names, |i, j| similarity_score(&lists[i], &lists[j])));
+
+    report.push_str("\nMissing values\n");
+    let sets: Vec<BTreeSet<i64>> = lists.iter().map(|list| list.iter().copied().collect()).collect();
+    for (i, set) in sets.iter().enumerate() {
+        for (j, other) in sets.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let missing: Vec<String> = set.difference(other).map(|value| value.to_string()).collect();
+            report.push_str(&format!("in {} but not {}: {}", names[i], names[j], missing.len()));
+            if !missing.is_empty() {
+                report.push_str(&format!(" ({})", missing.join(", ")));
+            }
+            report.push('\n');
+        }
+    }
+
+    report
+}
+
+fn format_matrix<F>(names: &[String], value: F) -> String
+where
+    F: Fn(usize, usize) -> i64,
+{
+    let cells: Vec<Vec<String>> = (0..names.len())
+        .map(|i| (0..names.len()).map(|j| value(i, j).to_string()).collect())
+        .collect();
+    let width = names
+        .iter()
+        .map(|name| name.len())
+        .chain(cells.iter().flatten().map(|cell| cell.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut matrix = format!("{:width$}", "", width = width);
+    for name in names {
+        matrix.push_str(&format!("  {:>width$}", name, width = width));
+    }
+    matrix.push('\n');
+    for (name, row) in names.iter().zip(&cells) {
+        matrix.push_str(&format!("{:width$}", name, width = width));
+        for cell in row {
+            matrix.push_str(&format!("  {:>width$}", cell, width = width));
+        }
+        matrix.push('\n');
+    }
+    matrix
+}
+
+// writes an input in the same shape as the real one but with `count` rows, for timing the solution on huge lists
+pub fn generate(path: &Path, count: u64, seed: u64) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut state = seed.max(1);
+
+    // xorshift, good enough for test data and keeps the output the same for the same seed
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut previous_left = Vec::with_capacity(1024);
+    for _ in 0..count {
+        let left = 10_000 + next() % 90_000;
+
+        // about half of the right list repeats a recent left number, so the similarity score isn't just 0
+        let right = if next() % 2 == 0 && !previous_left.is_empty() {
+            previous_left[(next() % previous_left.len() as u64) as usize]
+        } else {
+            10_000 + next() % 90_000
+        };
+
+        if previous_left.len() < 1024 {
+            previous_left.push(left);
+        } else {
+            previous_left[(next() % 1024) as usize] = left;
+        }
+
+        writeln!(writer, "{}   {}", left, right)?;
+    }
+
+    writer.flush()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> (Vec<i64>, Vec<i64>) {
+        (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3])
+    }
+
+    #[test]
+    fn total_distance_of_the_example() {
+        let (column1, column2) = example();
+        assert_eq!(total_distance(column1, column2), 11);
+    }
+
+    #[test]
+    fn similarity_score_of_the_example() {
+        let (column1, column2) = example();
+        assert_eq!(similarity_score(&column1, &column2), 31);
+    }
+
+    #[test]
+    fn reconcile_lists_of_different_lengths() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        let report = reconcile_lists(&names, &[vec![4, 1, 2, 3], vec![3, 1, 5]]);
+
+        // the distance only pairs up the 3 smallest of a, and 2 and 4 are only in a while 5 is only in b
+        let expected = "\
+a: 4 values
+b: 3 values
+
+Total distance
+   a  b
+a  0  3
+b  3  0
+
+Similarity score (row values counted in the column)
+     a   b
+a   10   4
+b    4   9
+
+Missing values
+in a but not b: 2 (2, 4)
+in b but not a: 1 (5)
+";
+        assert_eq!(report, expected);
+    }
+
+    #[test]
+    fn totals_past_i32() {
+        let big = i32::MAX as i64;
+        assert_eq!(total_distance(vec![0, 0], vec![big, big]), 2 * big);
+        assert_eq!(similarity_score(&[big, big], &[big, big, big]), 6 * big);
+    }
+}
@@ -1,5 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
+use image::RgbImage;
+
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette::{self, blend};
+use crate::utils::input::input_path;
 use crate::utils::read_as_matrix::read_as_matrix;
 
 struct Point {
@@ -8,7 +14,7 @@ struct Point {
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_as_matrix("src/day_10/input.txt");
+    let input = read_as_matrix(input_path("src/day_10/input.txt"));
 
     // convert from Vec<Vec<char>> to Vec<Vec<i32>>
     let matrix = input.iter().map(|row| {
@@ -32,7 +38,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_as_matrix("src/day_10/input.txt");
+    let input = read_as_matrix(input_path("src/day_10/input.txt"));
 
     // convert from Vec<Vec<char>> to Vec<Vec<i32>>
     let matrix = input.iter().map(|row| {
@@ -55,6 +61,44 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
+// draws the topographic map, darker is lower, with every distinct hiking trail on top
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let input = read_as_matrix(input_path("src/day_10/input.txt"));
+    let matrix = input.iter().map(|row| {
+        row.iter().map(|c| {
+            c.to_string().parse::<i32>().unwrap()
+        }).collect::<Vec<i32>>()
+    }).collect::<Vec<Vec<i32>>>();
+
+    let mut overlays = Vec::new();
+    for th in find_all_trailheads(&matrix) {
+        let mut trail = vec![(th.x as usize, th.y as usize)];
+        collect_trails(&matrix, &mut trail, &mut overlays);
+    }
+
+    Ok(renderer.render(&matrix, |&height| {
+        blend(palette::BACKGROUND, palette::GREEN, height as f32 / 9.0)
+    }, &overlays))
+}
+
+// depth first search that records every complete trail from the last cell in `trail` up to a 9
+fn collect_trails(matrix: &Vec<Vec<i32>>, trail: &mut Vec<(usize, usize)>, overlays: &mut Vec<Overlay>) {
+    let (x, y) = *trail.last().unwrap();
+    let current_height = matrix[y][x];
+    if current_height == 9 {
+        overlays.push(Overlay::Path { cells: trail.clone(), color: palette::GOLD });
+        return;
+    }
+
+    for (nx, ny) in neighbors(x as i32, y as i32, matrix[0].len(), matrix.len()) {
+        if matrix[ny as usize][nx as usize] == current_height + 1 {
+            trail.push((nx as usize, ny as usize));
+            collect_trails(matrix, trail, overlays);
+            trail.pop();
+        }
+    }
+}
+
 // a trailhead is anywhere on the matrix where the topo is 0
 fn find_all_trailheads(matrix: &Vec<Vec<i32>>) -> Vec<Point> {
     let mut trailheads = Vec::<Point>::new();
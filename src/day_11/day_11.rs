@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
+use crate::utils::input::input_path;
 use crate::utils::read_lines_as_int_arrays::read_lines_as_int_arrays;
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_lines_as_int_arrays("src/day_11/input.txt", " ")?;
+    let input = read_lines_as_int_arrays(input_path("src/day_11/input.txt"), " ")?;
 
     // input is just a single line, so take the first element
     let initial_stone_engravings = input[0].clone();
@@ -24,7 +25,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_lines_as_int_arrays("src/day_11/input.txt", " ")?;
+    let input = read_lines_as_int_arrays(input_path("src/day_11/input.txt"), " ")?;
 
     // input is just a single line, so take the first element
     let initial_stones = input[0].clone();
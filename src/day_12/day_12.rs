@@ -1,4 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use image::RgbImage;
+
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette::{self, distinct_color};
+use crate::utils::input::input_path;
 use crate::utils::read_as_matrix::read_as_matrix;
 use std::cmp::Ordering;
 
@@ -114,7 +120,7 @@ impl Region {
 
 pub fn run_a() -> std::io::Result<()> {
     
-    let input = read_as_matrix("src/day_12/input.txt");
+    let input = read_as_matrix(input_path("src/day_12/input.txt"));
 
     let regions = find_regions(&input);
 
@@ -127,7 +133,7 @@ pub fn run_a() -> std::io::Result<()> {
 
 pub fn run_b() -> std::io::Result<()> {
 
-    let input = read_as_matrix("src/day_12/input.txt");
+    let input = read_as_matrix(input_path("src/day_12/input.txt"));
 
     let regions = find_regions(&input);
 
@@ -138,6 +144,22 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
+// draws each region in its own colour, outlined where its fence would go
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let input = read_as_matrix(input_path("src/day_12/input.txt"));
+
+    let overlays: Vec<Overlay> = find_regions(&input)
+        .iter()
+        .enumerate()
+        .map(|(i, region)| Overlay::Region {
+            cells: region.garden_plots.iter().map(|p| (p.x as usize, p.y as usize)).collect(),
+            color: distinct_color(i),
+        })
+        .collect();
+
+    Ok(renderer.render_chars(&input, &palette::Palette::new(palette::BACKGROUND), &overlays))
+}
+
 // Given an area's cell count (A) and the number of internal adjacencies (E), compute perimeter.
 // Perimeter formula: P = 4*A - 2*E
 pub fn calculate_perimeter_from_area(area: i32, adjacencies: i32) -> i32 {
@@ -1,5 +1,5 @@
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_game_data("src/day_13/input.txt")?;
+    let input = read_game_data(input_path("src/day_13/input.txt"))?;
     println!("Total number of games: {}", input.len());
 
     let valid_games = find_valid_games(input);
@@ -12,7 +12,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_game_data("src/day_13/input.txt")?;
+    let input = read_game_data(input_path("src/day_13/input.txt"))?;
     println!("Total number of games: {}", input.len());
 
     let valid_games = find_valid_games_part2(input);
@@ -26,6 +26,7 @@ pub fn run_b() -> std::io::Result<()> {
 
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
+use crate::utils::input::input_path;
 
 #[derive(Debug)]
 struct Entry {
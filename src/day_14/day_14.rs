@@ -1,21 +1,44 @@
 use std::env;
-use std::fs;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
-use std::path::PathBuf;
+use std::time::Duration;
 use image::{RgbImage, Rgb};
-use rayon::prelude::*;
-use rusttype::{Font, Scale};
-use image::{ImageBuffer};
 
-// define const for tile width and height
-const TILES_WIDTH: i64 = 101;
-const TILES_HEIGHT: i64 = 103;
+use crate::render::animation::GifWriter;
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::terminal::TerminalAnimation;
+use crate::render::text::{draw_label, text_width, Label};
+use crate::utils::cancellation;
+use crate::utils::input::input_path;
+
+// the size of the space the drones move around in, in tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Board {
+    width: i64,
+    height: i64,
+}
+
+const BOARD: Board = Board { width: 101, height: 103 };
 
 // for example input (smaller examples :D)
-// const TILES_WIDTH: i64 = 11;
-// const TILES_HEIGHT: i64 = 7;
+const EXAMPLE_BOARD: Board = Board { width: 11, height: 7 };
+
+impl Board {
+    // the example's drones all start inside its smaller board, a real input's spread out over the whole of theirs
+    fn for_drones(drones: &[Drone]) -> Board {
+        let fits = |board: Board| {
+            drones
+                .iter()
+                .all(|drone| drone.position.x < board.width && drone.position.y < board.height)
+        };
+        if fits(EXAMPLE_BOARD) {
+            EXAMPLE_BOARD
+        } else {
+            BOARD
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Position {
@@ -44,149 +67,16 @@ impl Drone {
     }
 }
 
-// Simple 5x7 bitmap font for digits and some characters
-const DIGITS: [&[u8]; 10] = [
-    // 0
-    &[
-        0b01110,
-        0b10001,
-        0b10001,
-        0b10001,
-        0b10001,
-        0b10001,
-        0b01110,
-    ],
-    // 1
-    &[
-        0b00100,
-        0b01100,
-        0b00100,
-        0b00100,
-        0b00100,
-        0b00100,
-        0b01110,
-    ],
-    // 2
-    &[
-        0b01110,
-        0b10001,
-        0b00001,
-        0b00010,
-        0b00100,
-        0b01000,
-        0b11111,
-    ],
-    // 3
-    &[
-        0b01110,
-        0b10001,
-        0b00001,
-        0b00110,
-        0b00001,
-        0b10001,
-        0b01110,
-    ],
-    // 4
-    &[
-        0b00010,
-        0b00110,
-        0b01010,
-        0b10010,
-        0b11111,
-        0b00010,
-        0b00010,
-    ],
-    // 5
-    &[
-        0b11111,
-        0b10000,
-        0b11110,
-        0b00001,
-        0b00001,
-        0b10001,
-        0b01110,
-    ],
-    // 6
-    &[
-        0b01110,
-        0b10000,
-        0b10000,
-        0b11110,
-        0b10001,
-        0b10001,
-        0b01110,
-    ],
-    // 7
-    &[
-        0b11111,
-        0b00001,
-        0b00010,
-        0b00100,
-        0b01000,
-        0b01000,
-        0b01000,
-    ],
-    // 8
-    &[
-        0b01110,
-        0b10001,
-        0b10001,
-        0b01110,
-        0b10001,
-        0b10001,
-        0b01110,
-    ],
-    // 9
-    &[
-        0b01110,
-        0b10001,
-        0b10001,
-        0b01111,
-        0b00001,
-        0b10001,
-        0b01110,
-    ],
-];
-
-fn draw_number(img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>, num: usize, x: u32, y: u32) {
-    let digits = num.to_string();
-    let mut current_x = x;
-    
-    for digit in digits.chars() {
-        let digit_idx = (digit as u8 - b'0') as usize;
-        if digit_idx < DIGITS.len() {
-            let bitmap = DIGITS[digit_idx];
-            for (row, &bits) in bitmap.iter().enumerate() {
-                for col in 0..5 {
-                    if (bits & (1 << (4 - col))) != 0 {
-                        // Make the number 2x2 pixels for better visibility
-                        for dy in 0..2 {
-                            for dx in 0..2 {
-                                img.put_pixel(
-                                    current_x + (col as u32 * 2) + dx,
-                                    y + (row as u32 * 2) + dy,
-                                    Rgb([255, 255, 255])
-                                );
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        current_x += 12; // Space between digits
-    }
-}
-
 pub fn run_a() -> std::io::Result<()> {
-    let mut drones = read_drones("src/day_14/input.txt")?;
+    let mut drones = read_drones(input_path("src/day_14/input.txt"))?;
 
     // simulate drone movement and log grid snapshot
     for _ in 0..100 {
-        simulate_drone_movement(&mut drones);
+        simulate_drone_movement(&mut drones, BOARD);
     }
 
     // calculate safety factor
-    let safety_factor = calculate_safety_factor(&drones);
+    let safety_factor = calculate_safety_factor(&drones, BOARD);
 
     println!("Safety factor: {}", safety_factor);
     
@@ -195,17 +85,12 @@ pub fn run_a() -> std::io::Result<()> {
 
 pub fn run_b() -> std::io::Result<()> {
 
-    let mut drones = read_drones("src/day_14/input.txt")?;
+    let mut drones = read_drones(input_path("src/day_14/input.txt"))?;
 
-    let current_dir = env::current_dir()?;
-    let frames_dir = current_dir.join("drone_frames");
-    let output_video = current_dir.join("drone_simulation.mp4");
+    let output_gif = env::current_dir()?.join("drone_simulation.gif");
     
-    println!("Generating frames...");
-    generate_frame_images(&mut drones, &frames_dir, 10_000)?;
-    
-    println!("Converting to video...");
-    convert_frames_to_video(&frames_dir, &output_video)?;
+    println!("Rendering frames to {}...", output_gif.display());
+    write_drone_animation(&mut drones, &output_gif, 10_000)?;
     
     Ok(())
 }
@@ -255,7 +140,7 @@ pub fn read_drones<P: AsRef<Path>>(path: P) -> io::Result<Vec<Drone>> {
     Ok(drones)
 }
 
-fn simulate_drone_movement(drones: &mut [Drone]) {
+fn simulate_drone_movement(drones: &mut [Drone], board: Board) {
     // until a drone is at the edge of the grid, we keep moving the drones in their respective directions
     // however, at an edge, they will "teleport" i.e. to wrap around to the other side of the grid
     for drone in drones {
@@ -264,75 +149,40 @@ fn simulate_drone_movement(drones: &mut [Drone]) {
         drone.position.y += drone.velocity.dy;
 
         // Handle wrapping with modulo arithmetic to maintain continuous motion
-        // We add TILES_WIDTH/HEIGHT before taking modulo to handle negative numbers correctly
-        drone.position.x = ((drone.position.x + TILES_WIDTH) % TILES_WIDTH);
-        drone.position.y = ((drone.position.y + TILES_HEIGHT) % TILES_HEIGHT);
+        // We add the board's width/height before taking modulo to handle negative numbers correctly
+        drone.position.x = (drone.position.x + board.width) % board.width;
+        drone.position.y = (drone.position.y + board.height) % board.height;
     }
 }
 
-fn log_grid_snapshot(drones: &[Drone]) {
+fn grid_snapshot(drones: &[Drone], board: Board) -> String {
     // Create a grid that counts drones at each position
-    let mut grid = vec![vec![0; TILES_WIDTH as usize]; TILES_HEIGHT as usize];
+    let mut grid = vec![vec![0; board.width as usize]; board.height as usize];
 
     // Count drones at each position
     for drone in drones {
         grid[drone.position.y as usize][drone.position.x as usize] += 1;
     }
 
-    // Print the grid using numbers for multiple drones and '.' for empty spaces
+    // Draw the grid using numbers for multiple drones and '.' for empty spaces
+    let mut output = String::new();
     for row in grid {
-        let line: String = row.iter()
-            .map(|&count| if count == 0 {
-                '.'.to_string()
+        for count in row {
+            if count == 0 {
+                output.push('.');
             } else {
-                count.to_string()
-            })
-            .collect::<Vec<String>>()
-            .join("");
-        println!("{}", line);
-    }
-
-    println!();
-    println!();
-    println!();
-}
-
-fn log_quadrant_snapshot(drones: &[Drone]) {
-    let mut grid = vec![vec![0; TILES_WIDTH as usize]; TILES_HEIGHT as usize];
-
-    // Count drones at each position
-    for drone in drones {
-        grid[drone.position.y as usize][drone.position.x as usize] += 1;
-    }
-
-    // Print the grid, but leave middle lines empty
-    let mid_x = TILES_WIDTH / 2;
-    let mid_y = TILES_HEIGHT / 2;
-
-    for (y, row) in grid.iter().enumerate() {
-        let line: String = row.iter().enumerate()
-            .map(|(x, &count)| {
-                if x as i64 == mid_x || y as i64 == mid_y {
-                    ' '.to_string()
-                } else if count == 0 {
-                    '.'.to_string()
-                } else {
-                    count.to_string()
-                }
-            })
-            .collect::<Vec<String>>()
-            .join("");
-        println!("{}", line);
+                output.push_str(&count.to_string());
+            }
+        }
+        output.push('\n');
     }
 
-    println!();
-    println!();
-    println!();
+    output
 }
 
-fn calculate_safety_factor(drones: &[Drone]) -> i64 {
-    let mid_x = TILES_WIDTH / 2;
-    let mid_y = TILES_HEIGHT / 2;
+fn calculate_safety_factor(drones: &[Drone], board: Board) -> i64 {
+    let mid_x = board.width / 2;
+    let mid_y = board.height / 2;
     
     let mut quad1 = 0; // top-left
     let mut quad2 = 0; // top-right
@@ -356,75 +206,68 @@ fn calculate_safety_factor(drones: &[Drone]) -> i64 {
     quad1 * quad2 * quad3 * quad4
 }
 
-fn generate_frame_images(drones: &mut Vec<Drone>, output_dir: &PathBuf, num_frames: usize) -> io::Result<()> {
-    fs::create_dir_all(output_dir)?;
-    
-    let mut all_frames_positions = Vec::with_capacity(num_frames);
-    for frame in 0..num_frames {
-        let positions = drones.iter().map(|drone| {
-            (drone.position.x as usize, drone.position.y as usize)
-        }).collect::<Vec<_>>();
-        all_frames_positions.push(positions);
-        simulate_drone_movement(drones);
-    }
-    
-    all_frames_positions.par_iter().enumerate().for_each(|(frame, positions)| {
-        let mut img = ImageBuffer::new(
-            TILES_WIDTH as u32 * 4,
-            TILES_HEIGHT as u32 * 4 + 20  // Extra space for frame number
-        );
-        
-        // Draw frame number at the top
-        draw_number(&mut img, frame, 10, 5);
-        
-        // Draw drone positions
-        for &(x, y) in positions {
-            for dy in 0..4 {
-                for dx in 0..4 {
-                    let px = (x as u32 * 4) + dx;
-                    let py = (y as u32 * 4) + dy + 20;  // Offset for number space
-                    img.put_pixel(px, py, Rgb([255, 255, 255]));
-                }
-            }
-        }
-        
-        let frame_path = output_dir.join(format!("frame_{:05}.png", frame));
-        if let Err(e) = img.save(&frame_path) {
-            eprintln!("Error saving frame {}: {}", frame, e);
-        }
-        
-        if frame % 100 == 0 {
-            // println!("Generated frame {}", frame);
+// plays the drones moving in the terminal, one second per frame, for as many seconds as run_b renders
+pub fn animate(animation: &mut TerminalAnimation) -> io::Result<()> {
+    let mut drones = read_drones(input_path("src/day_14/input.txt"))?;
+
+    for second in 0..10_000 {
+        let status = format!("second {} | safety {}", second, calculate_safety_factor(&drones, BOARD));
+        if !animation.show(&grid_snapshot(&drones, BOARD), &status) {
+            break;
         }
-    });
-    
+        simulate_drone_movement(&mut drones, BOARD);
+    }
+
     Ok(())
 }
 
-fn convert_frames_to_video(frames_dir: &PathBuf, output_video: &PathBuf) -> io::Result<()> {
-    use std::process::Command;
-    
-    println!("Converting frames to video...");
-    
-    let status = Command::new("ffmpeg")
-        .args([
-            "-y",
-            "-framerate", "30",
-            "-i", &frames_dir.join("frame_%05d.png").to_string_lossy(),
-            "-c:v", "libx264",
-            "-preset", "ultrafast",
-            "-crf", "23",
-            "-pix_fmt", "yuv420p",
-            &output_video.to_string_lossy(),
-        ])
-        .status()?;
-    
-    if !status.success() {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "FFmpeg command failed"
-        ));
+// the drone grid after `steps` seconds, compared against golden files by the snapshot command
+pub fn snapshots<P: AsRef<Path>>(input: P, steps: usize) -> io::Result<Vec<(&'static str, String)>> {
+    let mut drones = read_drones(input)?;
+    let board = Board::for_drones(&drones);
+    for _ in 0..steps {
+        simulate_drone_movement(&mut drones, board);
     }
-    
+
+    Ok(vec![("drones", grid_snapshot(&drones, board))])
+}
+
+// each frame is encoded as soon as it's drawn, so only one frame is ever held in memory
+fn write_drone_animation(drones: &mut [Drone], output_gif: &Path, num_frames: usize) -> io::Result<()> {
+    // 4x4 pixels per tile, with space at the top for the labels
+    let renderer = GridRenderer::new(4).with_header(20);
+    let mut gif = GifWriter::create(output_gif, Duration::from_millis(33))?;
+    let token = cancellation::current();
+
+    for frame in 0..num_frames {
+        token.check()?;
+        gif.add_frame(&render_frame(&renderer, drones, frame))?;
+        simulate_drone_movement(drones, BOARD);
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+fn render_frame(renderer: &GridRenderer, drones: &[Drone], frame: usize) -> RgbImage {
+    let mut img = renderer.blank(BOARD.width as usize, BOARD.height as usize, Rgb([0, 0, 0]));
+
+    // Draw the frame number, safety factor and drone count along the top
+    let labels = [
+        format!("frame {}", frame),
+        format!("safety {}", calculate_safety_factor(drones, BOARD)),
+        format!("drones {}", drones.len()),
+    ];
+    let mut x = 10;
+    for text in labels {
+        let width = text_width(&text, 16.0) as i32;
+        draw_label(&mut img, &Label::new(text, x, 1, 16.0, Rgb([255, 255, 255])));
+        x += width + 20;
+    }
+
+    // Draw drone positions
+    for drone in drones {
+        renderer.fill_cell(&mut img, drone.position.x as usize, drone.position.y as usize, Rgb([255, 255, 255]));
+    }
+
+    img
+}
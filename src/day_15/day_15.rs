@@ -5,6 +5,11 @@ use std::path::Path;
 
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
+use crate::render::terminal::TerminalAnimation;
+use crate::utils::repl::{parse_count, ReplSession};
+use crate::utils::input::input_path;
+use crate::utils::stream_lines::{open_buffered, stream_grid_section, stream_lines};
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct Point {
     pub x: i32,
@@ -283,9 +288,9 @@ impl Warehouse {
         self.robot = next_robot_pos;
     }
 
-    // Parse function remains the same
+    // Parse the layout, then the moves after it
     fn parse<P: AsRef<Path>>(file_path: P) -> io::Result<(Self, Vec<Move>)> {
-        let content = fs::read_to_string(file_path)?;
+        let mut reader = open_buffered(file_path)?;
 
         let mut walls = HashSet::new();
         let mut boxes = HashSet::new();
@@ -294,11 +299,8 @@ impl Warehouse {
         let mut width = 0;
 
         // Parse warehouse layout
-        for (x, line) in content.lines().enumerate() {
-            if line.trim().is_empty() || !line.contains('#') {
-                break;
-            }
-            for (y, c) in line.chars().enumerate() {
+        for (x, line) in stream_grid_section(&mut reader).enumerate() {
+            for (y, c) in line?.into_iter().enumerate() {
                 match c {
                     '#' => {
                         walls.insert(Position { x, y });
@@ -317,13 +319,11 @@ impl Warehouse {
             height = x + 1;
         }
 
-        // Parse moves
-        let moves = content
-            .lines()
-            .skip_while(|line| line.contains('#') || line.trim().is_empty())
-            .flat_map(|line| line.chars())
-            .filter_map(Move::from_char)
-            .collect();
+        // Parse moves, everything after the layout
+        let mut moves = Vec::new();
+        for line in stream_lines(reader) {
+            moves.extend(line?.chars().filter_map(Move::from_char));
+        }
 
         Ok((
             Warehouse {
@@ -703,7 +703,7 @@ impl BigWarehouse {
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let (mut warehouse, moves) = Warehouse::parse("src/day_15/input.txt")?;
+    let (mut warehouse, moves) = Warehouse::parse(input_path("src/day_15/input.txt"))?;
 
     // Execute all moves
     for movement in moves {
@@ -718,132 +718,283 @@ pub fn run_a() -> std::io::Result<()> {
     Ok(())
 }
 
+// replays the robot's moves in the terminal, one move per frame
+pub fn animate(animation: &mut TerminalAnimation) -> std::io::Result<()> {
+    let (mut warehouse, moves) = Warehouse::parse(input_path("src/day_15/input.txt"))?;
+
+    let status = format!("move 0/{} | GPS sum {}", moves.len(), warehouse.calculate_gps_sum());
+    if !animation.show(&warehouse.generate_snapshot(), &status) {
+        return Ok(());
+    }
+
+    for (i, movement) in moves.iter().enumerate() {
+        warehouse.execute_move(movement);
+
+        let status = format!(
+            "move {}/{} ({:?}) | GPS sum {}",
+            i + 1,
+            moves.len(),
+            movement,
+            warehouse.calculate_gps_sum()
+        );
+        if !animation.show(&warehouse.generate_snapshot(), &status) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// both warehouses as text after their first `steps` moves, compared against golden files by the snapshot command
+pub fn snapshots<P: AsRef<Path>>(input: P, steps: usize) -> io::Result<Vec<(&'static str, String)>> {
+    let (mut warehouse, moves) = Warehouse::parse(&input)?;
+    for movement in moves.iter().take(steps) {
+        warehouse.execute_move(movement);
+    }
+
+    // the wide warehouse uses the same grid based simulation as run_b rather than BigWarehouse, which nothing runs
+    // and which drops a box on the example's 22nd move, so its snapshots would pin down the wrong behaviour
+    let content = fs::read_to_string(&input)?;
+    let (mut grid, moves, mut robot) = parse(&content, 2);
+    for &m in moves.into_iter().take(steps) {
+        robot = move_robot(&mut grid, robot, m);
+    }
+    grid[robot.y as usize][robot.x as usize] = b'@';
+
+    let mut wide_snapshot = String::new();
+    for row in &grid {
+        wide_snapshot.push_str(&String::from_utf8_lossy(row));
+        wide_snapshot.push('\n');
+    }
+
+    Ok(vec![("warehouse", warehouse.generate_snapshot()), ("big_warehouse", wide_snapshot)])
+}
+
+// the part A warehouse and its moves, for stepping through in the repl
+pub struct WarehouseSession {
+    warehouse: Warehouse,
+    moves: Vec<Move>,
+    next_move: usize,
+}
+
+impl WarehouseSession {
+    pub fn load() -> io::Result<Self> {
+        let (warehouse, moves) = Warehouse::parse(input_path("src/day_15/input.txt"))?;
+        Ok(WarehouseSession {
+            warehouse,
+            moves,
+            next_move: 0,
+        })
+    }
+
+    fn show(&self) -> String {
+        format!(
+            "{}after {}/{} moves, robot at row {} column {}",
+            self.warehouse.generate_snapshot(),
+            self.next_move,
+            self.moves.len(),
+            self.warehouse.robot.x,
+            self.warehouse.robot.y
+        )
+    }
+}
+
+impl ReplSession for WarehouseSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show", "draw the warehouse"),
+            ("step [n]", "apply the next n moves, 1 by default, and draw the warehouse"),
+            ("cell <row> <column>", "what is at the given position"),
+            ("gps", "the GPS sum of the boxes"),
+            ("reset", "reload the input and start again"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "show" => Ok(self.show()),
+            "step" => {
+                let count = parse_count(args)?;
+                let remaining = self.moves.len() - self.next_move;
+                if remaining == 0 {
+                    return Err("no moves left".to_string());
+                }
+
+                let applied = &self.moves[self.next_move..self.next_move + count.min(remaining)];
+                for movement in applied {
+                    self.warehouse.execute_move(movement);
+                }
+                let summary: Vec<String> = applied.iter().map(|m| format!("{:?}", m)).collect();
+                self.next_move += applied.len();
+
+                Ok(format!("moved {}\n{}", summary.join(", "), self.show()))
+            }
+            "cell" => {
+                let [row, column] = args else {
+                    return Err("usage: cell <row> <column>".to_string());
+                };
+                let pos = Position {
+                    x: row.parse().map_err(|_| format!("invalid row: {}", row))?,
+                    y: column.parse().map_err(|_| format!("invalid column: {}", column))?,
+                };
+                if pos.x >= self.warehouse.height || pos.y >= self.warehouse.width {
+                    return Err(format!("outside the {}x{} warehouse", self.warehouse.height, self.warehouse.width));
+                }
+
+                let contents = if self.warehouse.walls.contains(&pos) {
+                    "wall"
+                } else if self.warehouse.boxes.contains(&pos) {
+                    "box"
+                } else if self.warehouse.robot == pos {
+                    "robot"
+                } else {
+                    "empty"
+                };
+                Ok(contents.to_string())
+            }
+            "gps" => Ok(self.warehouse.calculate_gps_sum().to_string()),
+            "reset" => {
+                *self = Self::load().map_err(|e| e.to_string())?;
+                Ok(self.show())
+            }
+            _ => Err(format!("unknown command: {}", command)),
+        }
+    }
+}
+
 pub fn run_b() -> std::io::Result<()> {
-    let input = include_str!("../../src/day_15/input.txt");
+    let input = &fs::read_to_string(input_path("src/day_15/input.txt"))?;
     let (mut grid, moves, mut robot) = parse(input, 2);
 
     for &m in moves {
-        let direction = Point::from(m);
-        let next = robot + direction;
+        robot = move_robot(&mut grid, robot, m);
+    }
 
-        match grid[next.y as usize][next.x as usize] {
-            b'.' => {
-                // Trivially move the robot to the next spot if it's empty.
-                robot = next;
+    let result = coordinates(&grid);
+
+    println!("The GPS score is: {}", result);
+
+    Ok(())
+
+}
+
+// moves the robot one step in the wide warehouse, pushing any boxes in the way, and returns where it ends up
+fn move_robot(grid: &mut [Vec<u8>], mut robot: Point, m: u8) -> Point {
+    let direction = Point::from(m);
+    let next = robot + direction;
+
+    match grid[next.y as usize][next.x as usize] {
+        b'.' => {
+            // Trivially move the robot to the next spot if it's empty.
+            robot = next;
+        }
+        side @ b'[' | side @ b']' => {
+            // If a box is in the next spot, we need to then find all boxes and
+            // determine if it's possible to move them all.
+            let mut boxes = vec![next];
+
+            // Add the other side of the box to our list of boxes.
+            if side == b'[' {
+                boxes.push(next + Point::right());
+            } else {
+                boxes.push(next + Point::left());
             }
-            side @ b'[' | side @ b']' => {
-                // If a box is in the next spot, we need to then find all boxes and
-                // determine if it's possible to move them all.
-                let mut boxes = vec![next];
-
-                // Add the other side of the box to our list of boxes.
-                if side == b'[' {
-                    boxes.push(next + Point::right());
-                } else {
-                    boxes.push(next + Point::left());
-                }
 
-                // Search through boxes until we find them all.
-                let mut blocked = false;
-
-                match m {
-                    b'^' | b'v' => {
-                        // If we're moving up or down, finding the boxes is a little more complex
-                        // than left and right as we could have two boxes behind a single box.
-                        //
-                        // For example, if the robot is moving up in the scenario below, we need
-                        // to move both boxes behind that first box in front of the robot.
-                        //
-                        //     [][]
-                        //      []
-                        //       @
-                        //
-
-                        // Start the search with the first box.
-                        let mut current = boxes.clone();
-
-                        while current.len() > 1 {
-                            let mut next = Vec::new();
-
-                            for b in current {
-                                let path = b + direction;
-
-                                match grid[path.y as usize][path.x as usize] {
-                                    b'#' => {
-                                        // If we found a wall above/below the box we're searching, that
-                                        // means we're blocked, and we can stop the search here.
-                                        blocked = true;
-                                        next.clear();
-                                        break;
-                                    }
-                                    side @ b'[' | side @ b']' => {
-                                        // If we found another box, add it to our total list of boxes and
-                                        // the list of next boxes to search if we don't already have it.
-                                        if !next.contains(&path) {
-                                            boxes.push(path);
-                                            next.push(path);
-
-                                            if side == b'[' {
-                                                boxes.push(path + Point::right());
-                                                next.push(path + Point::right());
-                                            } else {
-                                                boxes.push(path + Point::left());
-                                                next.push(path + Point::left());
-                                            }
+            // Search through boxes until we find them all.
+            let mut blocked = false;
+
+            match m {
+                b'^' | b'v' => {
+                    // If we're moving up or down, finding the boxes is a little more complex
+                    // than left and right as we could have two boxes behind a single box.
+                    //
+                    // For example, if the robot is moving up in the scenario below, we need
+                    // to move both boxes behind that first box in front of the robot.
+                    //
+                    //     [][]
+                    //      []
+                    //       @
+                    //
+
+                    // Start the search with the first box.
+                    let mut current = boxes.clone();
+
+                    while current.len() > 1 {
+                        let mut next = Vec::new();
+
+                        for b in current {
+                            let path = b + direction;
+
+                            match grid[path.y as usize][path.x as usize] {
+                                b'#' => {
+                                    // If we found a wall above/below the box we're searching, that
+                                    // means we're blocked, and we can stop the search here.
+                                    blocked = true;
+                                    next.clear();
+                                    break;
+                                }
+                                side @ b'[' | side @ b']' => {
+                                    // If we found another box, add it to our total list of boxes and
+                                    // the list of next boxes to search if we don't already have it.
+                                    if !next.contains(&path) {
+                                        boxes.push(path);
+                                        next.push(path);
+
+                                        if side == b'[' {
+                                            boxes.push(path + Point::right());
+                                            next.push(path + Point::right());
+                                        } else {
+                                            boxes.push(path + Point::left());
+                                            next.push(path + Point::left());
                                         }
                                     }
-                                    _ => {}
                                 }
+                                _ => {}
                             }
-
-                            current = next;
-                        }
-                    }
-                    b'<' | b'>' => {
-                        // If we're moving left or right, finding all the boxes is simple as
-                        // we just need to search in a straight line, similar to part 1.
-                        //
-                        //     @[][]
-                        //
-
-                        // Start the search after the first box.
-                        let mut path = next + direction + direction;
-
-                        // Continue searching while we're finding boxes.
-                        while [b'[', b']'].contains(&grid[path.y as usize][path.x as usize]) {
-                            boxes.push(path);
-                            path += direction;
                         }
 
-                        // If the first non-box spot is not empty, we are blocked from moving.
-                        if grid[path.y as usize][path.x as usize] != b'.' {
-                            blocked = true;
-                        }
+                        current = next;
                     }
-                    _ => {}
                 }
+                b'<' | b'>' => {
+                    // If we're moving left or right, finding all the boxes is simple as
+                    // we just need to search in a straight line, similar to part 1.
+                    //
+                    //     @[][]
+                    //
+
+                    // Start the search after the first box.
+                    let mut path = next + direction + direction;
+
+                    // Continue searching while we're finding boxes.
+                    while [b'[', b']'].contains(&grid[path.y as usize][path.x as usize]) {
+                        boxes.push(path);
+                        path += direction;
+                    }
 
-                // Move all the boxes and the robot only if we're not blocked.
-                if !blocked {
-                    for &b in boxes.iter().rev() {
-                        let mov = b + direction;
-                        grid[mov.y as usize][mov.x as usize] = grid[b.y as usize][b.x as usize];
-                        grid[b.y as usize][b.x as usize] = b'.';
+                    // If the first non-box spot is not empty, we are blocked from moving.
+                    if grid[path.y as usize][path.x as usize] != b'.' {
+                        blocked = true;
                     }
+                }
+                _ => {}
+            }
 
-                    robot = next;
+            // Move all the boxes and the robot only if we're not blocked.
+            if !blocked {
+                for &b in boxes.iter().rev() {
+                    let mov = b + direction;
+                    grid[mov.y as usize][mov.x as usize] = grid[b.y as usize][b.x as usize];
+                    grid[b.y as usize][b.x as usize] = b'.';
                 }
+
+                robot = next;
             }
-            _ => {}
         }
+        _ => {}
     }
 
-    let result = coordinates(&grid);
-
-    println!("The GPS score is: {}", result);
-
-    Ok(())
-
+    robot
 }
 
 fn parse(input: &str, part: u8) -> (Vec<Vec<u8>>, Vec<&u8>, Point) {
@@ -1,3 +1,9 @@
+use image::RgbImage;
+
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette::{self, Palette};
+use crate::utils::input::input_path;
 use crate::utils::read_as_matrix::read_as_matrix;
 use std::collections::{BinaryHeap, HashSet, HashMap};
 use std::cmp::Ordering;
@@ -201,7 +207,7 @@ fn solve_maze_all_optimal_paths(grid: &Vec<Vec<char>>) -> (Option<u32>, HashSet<
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_as_matrix("src/day_16/input.txt");
+    let input = read_as_matrix(input_path("src/day_16/input.txt"));
     
     let (min_score, _) = solve_maze_all_optimal_paths(&input);
     if let Some(result) = min_score {
@@ -214,10 +220,23 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_as_matrix("src/day_16/input.txt");
+    let input = read_as_matrix(input_path("src/day_16/input.txt"));
     
     let (_, optimal_tiles) = solve_maze_all_optimal_paths(&input);
     println!("Number of tiles on optimal paths: {}", optimal_tiles.len());
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// draws the maze with every tile that is on at least one of the best paths
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let input = read_as_matrix(input_path("src/day_16/input.txt"));
+    
+    let (_, optimal_tiles) = solve_maze_all_optimal_paths(&input);
+    let overlays = [Overlay::Trail {
+        cells: optimal_tiles.iter().map(|&(row, col)| (col, row)).collect(),
+        color: palette::GOLD,
+    }];
+
+    Ok(renderer.render_chars(&input, &Palette::maze(), &overlays))
+}
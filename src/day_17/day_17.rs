@@ -1,6 +1,9 @@
 use std::str::FromStr;
+use std::fmt;
 use std::fs;
 use std::path::Path;
+use crate::utils::input::input_path;
+use crate::utils::repl::{parse_count, ReplSession};
 
 #[derive(Debug)]
 struct StrangeDevice {
@@ -10,6 +13,8 @@ struct StrangeDevice {
     instruction_pointer: usize,
     program: Vec<i64>,
     output: Vec<i64>,
+    // print the registers after every instruction
+    trace: bool,
 }
 
 #[derive(Debug)]
@@ -17,6 +22,19 @@ pub enum ParseError {
     InvalidFormat,
     InvalidNumber,
     FileError(std::io::Error),
+    // the program hit an instruction it can't run
+    InvalidProgram(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidFormat => write!(f, "Invalid format"),
+            ParseError::InvalidNumber => write!(f, "Invalid number"),
+            ParseError::FileError(err) => write!(f, "File error: {}", err),
+            ParseError::InvalidProgram(message) => write!(f, "Invalid program: {}", message),
+        }
+    }
 }
 
 impl From<std::io::Error> for ParseError {
@@ -71,6 +89,7 @@ impl FromStr for StrangeDevice {
             instruction_pointer: 0,
             program,
             output: Vec::new(),
+            trace: true,
         })
     }
 }
@@ -81,20 +100,29 @@ impl StrangeDevice {
         Self::from_str(&contents)
     }
 
-    fn get_combo_operand_value(&self, operand: i64) -> i64 {
+    fn get_combo_operand_value(&self, operand: i64) -> Result<i64, String> {
         match operand {
-            0..=3 => operand,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            7 => panic!("Invalid combo operand 7"),
-            _ => panic!("Invalid combo operand"),
+            0..=3 => Ok(operand),
+            4 => Ok(self.register_a),
+            5 => Ok(self.register_b),
+            6 => Ok(self.register_c),
+            _ => Err(format!("invalid combo operand {} at {}", operand, self.instruction_pointer)),
         }
     }
 
-    fn execute_instruction(&mut self) -> bool {
-        if self.instruction_pointer >= self.program.len() {
-            return false;
+    // register A divided by 2 to the power of the operand. the registers are never negative, so a power too big to
+    // shift by just leaves 0
+    fn divide_a(&self, power: i64) -> Result<i64, String> {
+        let power = u32::try_from(power)
+            .map_err(|_| format!("can't divide by 2 to the power of {} at {}", power, self.instruction_pointer))?;
+        Ok(self.register_a.checked_shr(power).unwrap_or(0))
+    }
+
+    // false once the program has halted. a bad opcode or operand is an error rather than a panic, so a repl session
+    // that set the registers to something odd can carry on
+    fn execute_instruction(&mut self) -> Result<bool, String> {
+        if self.instruction_pointer + 1 >= self.program.len() {
+            return Ok(false);
         }
 
         let opcode = self.program[self.instruction_pointer];
@@ -102,15 +130,8 @@ impl StrangeDevice {
 
         match opcode {
             0 => { // adv
-                let power = match operand {
-                    0..=3 => operand,
-                    4 => self.register_a,
-                    5 => self.register_b,
-                    6 => self.register_c,
-                    _ => panic!("Invalid operand")
-                } % 8;
-                let divisor = 2_i64.pow(power as u32);
-                self.register_a = self.register_a / divisor;
+                let power = self.get_combo_operand_value(operand)? % 8;
+                self.register_a = self.divide_a(power)?;
                 self.instruction_pointer += 2;
             },
             1 => { // bxl
@@ -118,12 +139,13 @@ impl StrangeDevice {
                 self.instruction_pointer += 2;
             },
             2 => { // bst
-                self.register_b = self.get_combo_operand_value(operand) % 8;
+                self.register_b = self.get_combo_operand_value(operand)? % 8;
                 self.instruction_pointer += 2;
             },
             3 => { // jnz
                 if self.register_a != 0 {
-                    self.instruction_pointer = operand as usize;
+                    self.instruction_pointer = usize::try_from(operand)
+                        .map_err(|_| format!("can't jump to {} at {}", operand, self.instruction_pointer))?;
                 } else {
                     self.instruction_pointer += 2;
                 }
@@ -133,27 +155,27 @@ impl StrangeDevice {
                 self.instruction_pointer += 2;
             },
             5 => { // out
-                let value = self.get_combo_operand_value(operand) % 8;
+                let value = self.get_combo_operand_value(operand)? % 8;
                 self.output.push(value);
                 self.instruction_pointer += 2;
             },
             6 => { // bdv
-                let power = self.get_combo_operand_value(operand);
-                let divisor = 2_i64.pow(power as u32);
-                self.register_b = self.register_a / divisor;
+                let power = self.get_combo_operand_value(operand)?;
+                self.register_b = self.divide_a(power)?;
                 self.instruction_pointer += 2;
             },
             7 => { // cdv
-                let power = self.get_combo_operand_value(operand);
-                let divisor = 2_i64.pow(power as u32);
-                self.register_c = self.register_a / divisor;
+                let power = self.get_combo_operand_value(operand)?;
+                self.register_c = self.divide_a(power)?;
                 self.instruction_pointer += 2;
             },
-            _ => panic!("Invalid opcode"),
+            _ => return Err(format!("invalid opcode {} at {}", opcode, self.instruction_pointer)),
         }
 
-        println!("After - A: {}, B: {}, C: {}", self.register_a, self.register_b, self.register_c);
-        true
+        if self.trace {
+            println!("After - A: {}, B: {}, C: {}", self.register_a, self.register_b, self.register_c);
+        }
+        Ok(true)
     }
 
     fn get_program_output(&self) -> String {
@@ -163,16 +185,138 @@ impl StrangeDevice {
             .join(",")
     }
 
-    fn run(&mut self) -> String {
-        while self.execute_instruction() {}
-        self.get_program_output()
+    fn run(&mut self) -> Result<String, String> {
+        while self.execute_instruction()? {}
+        Ok(self.get_program_output())
+    }
+}
+
+const INSTRUCTION_NAMES: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+// the device from the input, for stepping through its program in the repl
+pub struct DeviceSession {
+    device: StrangeDevice,
+}
+
+impl DeviceSession {
+    pub fn load() -> Result<Self, ParseError> {
+        let mut device = StrangeDevice::from_file(input_path("src/day_17/input.txt"))?;
+        // the session shows the registers itself
+        device.trace = false;
+        Ok(DeviceSession { device })
+    }
+
+    fn registers(&self) -> String {
+        format!(
+            "A: {}, B: {}, C: {}, ip: {}",
+            self.device.register_a, self.device.register_b, self.device.register_c, self.device.instruction_pointer
+        )
+    }
+
+    fn instruction_at(&self, ip: usize) -> String {
+        match (self.device.program.get(ip), self.device.program.get(ip + 1)) {
+            (Some(&opcode), Some(&operand)) => {
+                let name = INSTRUCTION_NAMES.get(opcode as usize).unwrap_or(&"???");
+                format!("{} {}", name, operand)
+            }
+            _ => "halt".to_string(),
+        }
+    }
+
+    fn halted(&self) -> bool {
+        self.device.instruction_pointer + 1 >= self.device.program.len()
+    }
+}
+
+impl ReplSession for DeviceSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("regs", "show the registers and instruction pointer"),
+            ("step [n]", "execute the next n instructions, 1 by default"),
+            ("run [limit]", "execute until the program halts, at most 1000000 instructions by default"),
+            ("out", "the output so far"),
+            ("program", "list the program, marking the next instruction"),
+            ("set <a|b|c> <value>", "change a register"),
+            ("reset", "reload the input and start again"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "regs" => Ok(self.registers()),
+            "step" => {
+                let mut output = String::new();
+                for _ in 0..parse_count(args)? {
+                    if self.halted() {
+                        output.push_str("halted\n");
+                        break;
+                    }
+                    let instruction = self.instruction_at(self.device.instruction_pointer);
+                    self.device.execute_instruction()?;
+                    output.push_str(&format!("{:<6} -> {}\n", instruction, self.registers()));
+                }
+                Ok(output)
+            }
+            "run" => {
+                let limit = match args.first() {
+                    Some(_) => parse_count(args)?,
+                    None => 1_000_000,
+                };
+                let mut executed = 0;
+                while executed < limit && !self.halted() {
+                    self.device.execute_instruction()?;
+                    executed += 1;
+                }
+                let state = if self.halted() { "halted" } else { "stopped at the limit" };
+                Ok(format!(
+                    "{} after {} instructions\n{}\noutput: {}",
+                    state,
+                    executed,
+                    self.registers(),
+                    self.device.get_program_output()
+                ))
+            }
+            "out" => Ok(self.device.get_program_output()),
+            "program" => {
+                let listing: Vec<String> = (0..self.device.program.len())
+                    .step_by(2)
+                    .map(|ip| {
+                        let marker = if ip == self.device.instruction_pointer { ">" } else { " " };
+                        format!("{} {:>3}  {}", marker, ip, self.instruction_at(ip))
+                    })
+                    .collect();
+                Ok(listing.join("\n"))
+            }
+            "set" => {
+                let [register, value] = args else {
+                    return Err("usage: set <a|b|c> <value>".to_string());
+                };
+                // the device only ever divides and xors non-negative numbers
+                let value: i64 = match value.parse() {
+                    Ok(value) if value >= 0 => value,
+                    _ => return Err(format!("invalid value: {} (expected 0 to {})", value, i64::MAX)),
+                };
+                match register.to_ascii_lowercase().as_str() {
+                    "a" => self.device.register_a = value,
+                    "b" => self.device.register_b = value,
+                    "c" => self.device.register_c = value,
+                    _ => return Err(format!("unknown register: {}", register)),
+                }
+                Ok(self.registers())
+            }
+            "reset" => {
+                *self = Self::load().map_err(|e| e.to_string())?;
+                Ok(self.registers())
+            }
+            _ => Err(format!("unknown command: {}", command)),
+        }
     }
 }
 
 pub fn run_a() -> Result<(), ParseError> {
-    let mut strange_device = StrangeDevice::from_file("src/day_17/input.txt")?;
+    let mut strange_device = StrangeDevice::from_file(input_path("src/day_17/input.txt"))?;
 
-    let output = strange_device.run();
+    let output = strange_device.run().map_err(ParseError::InvalidProgram)?;
 
     // print out the comma joined string
     println!("{}", output.replace(",", ""));
@@ -182,7 +326,7 @@ pub fn run_a() -> Result<(), ParseError> {
 
 pub fn run_b() -> Result<(), ParseError> {
     // Read and parse the program
-    let device = StrangeDevice::from_file("src/day_17/input.txt")?;
+    let device = StrangeDevice::from_file(input_path("src/day_17/input.txt"))?;
     let program = device.program;
     
     // Find the minimum value for register A
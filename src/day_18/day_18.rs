@@ -1,6 +1,13 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
 
+use image::RgbImage;
+
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette;
+use crate::utils::input::input_path;
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 struct State {
     cost: u32,
@@ -83,6 +90,44 @@ fn find_shortest_path(grid: &Vec<Vec<bool>>, size: usize) -> Option<u32> {
     None
 }
 
+// breadth first search like find_shortest_path, but remembering where we came from so the route can be drawn
+fn find_shortest_route(grid: &[Vec<bool>], size: usize) -> Option<Vec<(usize, usize)>> {
+    let start = (0, 0);
+    let goal = (size - 1, size - 1);
+
+    if grid[start.0][start.1] || grid[goal.0][goal.1] {
+        return None;
+    }
+
+    let mut came_from = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    came_from.insert(start, start);
+
+    while let Some(position) = queue.pop_front() {
+        if position == goal {
+            let mut route = vec![goal];
+            let mut current = goal;
+            while current != start {
+                current = came_from[&current];
+                route.push(current);
+            }
+            route.reverse();
+            return Some(route);
+        }
+
+        for next_pos in get_neighbors(position, size) {
+            if came_from.contains_key(&next_pos) || grid[next_pos.0][next_pos.1] {
+                continue;
+            }
+            came_from.insert(next_pos, position);
+            queue.push_back(next_pos);
+        }
+    }
+
+    None
+}
+
 fn solve_with_bytes(input: &str, size: usize, num_bytes: usize) -> Option<u32> {
     let grid = parse_input(input, size, num_bytes);
     find_shortest_path(&grid, size)
@@ -124,7 +169,7 @@ const NUM_BYTES_FALLEN: usize = 1024;
 // const NUM_BYTES_FALLEN: usize = 12;
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = std::fs::read_to_string(INPUT_PATH)?;
+    let input = std::fs::read_to_string(input_path(INPUT_PATH))?;
     
     // Use first 1024 bytes for part A
     if let Some(steps) = solve_with_bytes(&input, GRID_SIZE, NUM_BYTES_FALLEN) {
@@ -137,7 +182,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = std::fs::read_to_string(INPUT_PATH)?;
+    let input = std::fs::read_to_string(input_path(INPUT_PATH))?;
     
     if let Some((x, y)) = find_blocking_byte(&input, GRID_SIZE) {
         println!("{},{}", x, y);
@@ -146,4 +191,22 @@ pub fn run_b() -> std::io::Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// draws the memory space after the part A bytes have fallen, with the shortest route to the exit
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let input = std::fs::read_to_string(input_path(INPUT_PATH))?;
+    let grid = parse_input(&input, GRID_SIZE, NUM_BYTES_FALLEN);
+
+    let mut overlays = Vec::new();
+    if let Some(route) = find_shortest_route(&grid, GRID_SIZE) {
+        overlays.push(Overlay::Path {
+            cells: route.iter().map(|&(row, col)| (col, row)).collect(),
+            color: palette::GOLD,
+        });
+    }
+
+    Ok(renderer.render(&grid, |&corrupted| {
+        if corrupted { palette::WALL } else { palette::BACKGROUND }
+    }, &overlays))
+}
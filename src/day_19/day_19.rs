@@ -2,6 +2,7 @@ use std::fs;
 use std::path::Path;
 use std::str::FromStr;
 use std::collections::HashMap;
+use crate::utils::input::input_path;
 
 #[derive(Debug)]
 struct ParseError;
@@ -42,7 +43,7 @@ impl FromStr for TowelPatterns {
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = fs::read_to_string(Path::new("./src/day_19/input.txt"))?;
+    let input = fs::read_to_string(Path::new(input_path("./src/day_19/input.txt")))?;
     let patterns = TowelPatterns::from_str(&input)
         .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Parse error"))?;
     
@@ -60,7 +61,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = fs::read_to_string(Path::new("./src/day_19/input.txt"))?;
+    let input = fs::read_to_string(Path::new(input_path("./src/day_19/input.txt")))?;
     let patterns = TowelPatterns::from_str(&input)
         .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Parse error"))?;
     
@@ -1,15 +1,19 @@
-use crate::utils::read_as_rows::read_as_rows;
+use std::ops::RangeInclusive;
+
+use crate::utils::input::input_path;
+use crate::utils::report::{format_table, ReportFormat};
+use crate::utils::stream_lines::{open_buffered, stream_int_rows};
 
 pub fn run_a() -> std::io::Result<()> {
-    // read the input file into rows
-    let rows = read_as_rows("src/day_2/input.txt")?;
-    let rows_int: Vec<Vec<i32>> = rows.iter().map(|row| row.iter().map(|s| s.parse::<i32>().unwrap()).collect()).collect();
+    // read the input file a row at a time
+    let reader = open_buffered(input_path("src/day_2/input.txt"))?;
 
     // count the number of safe rows
+    let dampener = Dampener::new(0, SAFE_STEPS);
     let mut safe_rows = 0;
-    for row in rows_int {
-        let is_safe_result = is_safe(&row);
-        if is_safe_result {
+    for row in stream_int_rows::<_, i32>(reader, None) {
+        let row = row?;
+        if dampener.is_safe(&row) {
             safe_rows += 1;
         }
     }
@@ -20,21 +24,16 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    // read the input file into rows
-    let rows = read_as_rows("src/day_2/input.txt")?;
-    let rows_int: Vec<Vec<i32>> = rows.iter().map(|row| row.iter().map(|s| s.parse::<i32>().unwrap()).collect()).collect();
+    // read the input file a row at a time
+    let reader = open_buffered(input_path("src/day_2/input.txt"))?;
 
     // count the number of safe rows, but also those that are safe with one element removed
+    let dampener = Dampener::new(1, SAFE_STEPS);
     let mut safe_rows = 0;
-    for row in rows_int {
-        let is_safe_result = is_safe(&row);
-        if is_safe_result {
+    for row in stream_int_rows::<_, i32>(reader, None) {
+        let row = row?;
+        if dampener.is_safe(&row) {
             safe_rows += 1;
-        } else {
-            let is_safe_with_problem_dampener_result = is_safe_with_problem_dampener(&row);
-            if is_safe_with_problem_dampener_result {
-                safe_rows += 1;
-            }
         }
     }
 
@@ -43,37 +42,391 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
-fn is_safe(row: &Vec<i32>) -> bool {
-    // Check if monotonically increasing
-    let mut increasing = true;
-    for i in 1..row.len() {
-        if row[i] <= row[i-1] || row[i] - row[i-1] > 3 {
-            increasing = false;
-            break;
+// how far apart two neighbouring levels may be, in whichever direction the report is going
+const SAFE_STEPS: RangeInclusive<i32> = 1..=3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+impl Direction {
+    fn name(&self) -> &'static str {
+        match self {
+            Direction::Increasing => "increasing",
+            Direction::Decreasing => "decreasing",
+        }
+    }
+}
+
+// why a report isn't safe as it stands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Violation {
+    // goes the other way to the first step
+    NotMonotonic,
+    ZeroStep,
+    StepTooSmall,
+    StepTooLarge,
+}
+
+impl Violation {
+    fn name(&self) -> &'static str {
+        match self {
+            Violation::NotMonotonic => "not monotonic",
+            Violation::ZeroStep => "zero step",
+            Violation::StepTooSmall => "step too small",
+            Violation::StepTooLarge => "step too large",
+        }
+    }
+}
+
+struct Verdict {
+    // counted from 1, in the order of the input
+    report: usize,
+    levels: Vec<i32>,
+    // the way the first step goes, None if there's no step or it's flat
+    direction: Option<Direction>,
+    // the index of the first level that breaks the rules and why
+    violation: Option<(usize, Violation)>,
+    // the levels the dampener ignores to make it safe, None if it can't
+    removed: Option<Vec<usize>>,
+}
+
+impl Verdict {
+    fn new(report: usize, levels: Vec<i32>, dampener: &Dampener) -> Self {
+        let direction = match levels.get(..2) {
+            Some([a, b]) if b > a => Some(Direction::Increasing),
+            Some([a, b]) if b < a => Some(Direction::Decreasing),
+            _ => None,
+        };
+        let violation = first_violation(&levels, direction, &dampener.steps);
+        let removed = if violation.is_some() { dampener.removed_levels(&levels) } else { Some(Vec::new()) };
+
+        Verdict {
+            report,
+            levels,
+            direction,
+            violation,
+            removed,
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match (&self.violation, &self.removed) {
+            (None, _) => "safe",
+            (Some(_), Some(_)) => "dampened",
+            (Some(_), None) => "unsafe",
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let levels: Vec<String> = self.levels.iter().map(|level| level.to_string()).collect();
+        let direction = self.direction.map_or("null".to_string(), |d| format!("\"{}\"", d.name()));
+        let violation = match self.violation {
+            Some((index, reason)) => format!("{{\"index\": {}, \"reason\": \"{}\"}}", index, reason.name()),
+            None => "null".to_string(),
+        };
+        let removed = match &self.removed {
+            Some(removed) => {
+                let removed: Vec<String> = removed.iter().map(|index| index.to_string()).collect();
+                format!("[{}]", removed.join(", "))
+            }
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{{\"report\": {}, \"levels\": [{}], \"direction\": {}, \"status\": \"{}\", \"violation\": {}, \"removed\": {}}}",
+            self.report,
+            levels.join(", "),
+            direction,
+            self.status(),
+            violation,
+            removed
+        )
+    }
+
+    fn table_row(&self) -> Vec<String> {
+        let levels: Vec<String> = self.levels.iter().map(|level| level.to_string()).collect();
+        let violation = match self.violation {
+            Some((index, reason)) => format!("{} at index {}", reason.name(), index),
+            None => "-".to_string(),
+        };
+        let removed = match &self.removed {
+            Some(removed) if !removed.is_empty() => {
+                let removed: Vec<String> = removed
+                    .iter()
+                    .map(|&index| format!("index {} ({})", index, self.levels[index]))
+                    .collect();
+                removed.join(", ")
+            }
+            _ => "-".to_string(),
+        };
+
+        vec![
+            self.report.to_string(),
+            levels.join(" "),
+            self.direction.map_or("-", |d| d.name()).to_string(),
+            self.status().to_string(),
+            violation,
+            removed,
+        ]
+    }
+}
+
+// walks the steps in the direction of the first one, stopping at the first that isn't allowed
+fn first_violation(levels: &[i32], direction: Option<Direction>, steps: &RangeInclusive<i32>) -> Option<(usize, Violation)> {
+    for i in 1..levels.len() {
+        let step = levels[i] - levels[i - 1];
+        let reason = if step == 0 {
+            Violation::ZeroStep
+        } else if (step > 0) != (direction == Some(Direction::Increasing)) {
+            Violation::NotMonotonic
+        } else if step.abs() < *steps.start() {
+            Violation::StepTooSmall
+        } else if step.abs() > *steps.end() {
+            Violation::StepTooLarge
+        } else {
+            continue;
+        };
+        return Some((i, reason));
+    }
+    None
+}
+
+// why each report is or isn't safe with part B's dampener, for seeing which readings get rejected
+pub fn report(format: ReportFormat) -> std::io::Result<String> {
+    let reader = open_buffered(input_path("src/day_2/input.txt"))?;
+    let dampener = Dampener::new(1, SAFE_STEPS);
+
+    let mut verdicts = Vec::new();
+    for (i, row) in stream_int_rows::<_, i32>(reader, None).enumerate() {
+        verdicts.push(Verdict::new(i + 1, row?, &dampener));
+    }
+
+    Ok(match format {
+        ReportFormat::Json => {
+            let objects: Vec<String> = verdicts.iter().map(|verdict| format!("  {}", verdict.to_json())).collect();
+            format!("[\n{}\n]\n", objects.join(",\n"))
         }
+        ReportFormat::Table => {
+            let mut rows = vec![["Report", "Levels", "Direction", "Status", "First violation", "Removed"]
+                .iter()
+                .map(|header| header.to_string())
+                .collect::<Vec<String>>()];
+            rows.extend(verdicts.iter().map(Verdict::table_row));
+            format_table(&rows)
+        }
+    })
+}
+
+// decides whether a report is safe once up to max_removals of its levels are ignored
+struct Dampener {
+    max_removals: usize,
+    steps: RangeInclusive<i32>,
+}
+
+impl Dampener {
+    fn new(max_removals: usize, steps: RangeInclusive<i32>) -> Self {
+        Dampener { max_removals, steps }
     }
-    
-    // Check if monotonically decreasing
-    let mut decreasing = true;
-    for i in 1..row.len() {
-        if row[i] >= row[i-1] || row[i-1] - row[i] > 3 {
-            decreasing = false;
-            break;
+
+    fn is_safe(&self, row: &[i32]) -> bool {
+        self.removed_levels(row).is_some()
+    }
+
+    // the indices of the fewest levels to remove to make the report safe, None if that's more than allowed
+    fn removed_levels(&self, row: &[i32]) -> Option<Vec<usize>> {
+        [Direction::Increasing, Direction::Decreasing]
+            .iter()
+            .map(|&direction| self.fewest_removals(row, direction))
+            .min_by_key(|removed| removed.len())
+            .filter(|removed| removed.len() <= self.max_removals)
+    }
+
+    fn step_allowed(&self, from: i32, to: i32, direction: Direction) -> bool {
+        let step = match direction {
+            Direction::Increasing => to - from,
+            Direction::Decreasing => from - to,
+        };
+        self.steps.contains(&step)
+    }
+
+    // the fewest levels to remove so the rest all step in the given direction. removals[i] is the fewest
+    // to remove before i when i is kept, and only the previous max_removals + 1 levels can come before it
+    // without going over the limit, so this is O(n * k) rather than trying every combination
+    fn fewest_removals(&self, row: &[i32], direction: Direction) -> Vec<usize> {
+        if row.is_empty() {
+            return Vec::new();
+        }
+
+        let mut removals = vec![0; row.len()];
+        // the level kept before each one, None if it's the first kept
+        let mut previous: Vec<Option<usize>> = vec![None; row.len()];
+        for i in 0..row.len() {
+            // keeping nothing before i
+            removals[i] = i;
+            for j in i.saturating_sub(self.max_removals + 1)..i {
+                if self.step_allowed(row[j], row[i], direction) && removals[j] + (i - j - 1) < removals[i] {
+                    removals[i] = removals[j] + (i - j - 1);
+                    previous[i] = Some(j);
+                }
+            }
         }
+
+        // and everything after the last level kept goes too
+        let last = (0..row.len())
+            .min_by_key(|&i| removals[i] + (row.len() - 1 - i))
+            .unwrap_or(0);
+
+        let mut kept = vec![false; row.len()];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = previous[i];
+        }
+        (0..row.len()).filter(|&i| !kept[i]).collect()
     }
-    
-    // Must be either monotonically increasing or all decreasing
-    increasing || decreasing
 }
 
-fn is_safe_with_problem_dampener(row: &Vec<i32>) -> bool {
-    // check if is_safe with any one element removed
-    for i in 0..row.len() {
-        let mut row_copy = row.clone();
-        row_copy.remove(i);
-        if is_safe(&row_copy) {
-            return true;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn safe_as_is(levels: &[i32]) -> bool {
+        let dampener = Dampener::new(0, SAFE_STEPS);
+        [Direction::Increasing, Direction::Decreasing]
+            .iter()
+            .any(|&direction| levels.windows(2).all(|pair| dampener.step_allowed(pair[0], pair[1], direction)))
+    }
+
+    // the fewest levels that can be removed to leave a safe report, by trying every combination
+    fn brute_force_fewest(row: &[i32]) -> usize {
+        (0u32..1 << row.len())
+            .filter(|removed| {
+                let kept: Vec<i32> = (0..row.len()).filter(|i| removed & (1 << i) == 0).map(|i| row[i]).collect();
+                safe_as_is(&kept)
+            })
+            .map(|removed| removed.count_ones() as usize)
+            .min()
+            .unwrap()
+    }
+
+    fn check_against_brute_force(row: &[i32], max_removals: usize) {
+        let removed = Dampener::new(max_removals, SAFE_STEPS).removed_levels(row);
+        let fewest = brute_force_fewest(row);
+
+        if fewest > max_removals {
+            assert_eq!(removed, None, "{:?} with {} removals", row, max_removals);
+            return;
+        }
+        let removed = removed.unwrap_or_else(|| panic!("{:?} is safe with {} removals", row, fewest));
+        assert_eq!(removed.len(), fewest, "{:?} removed {:?}", row, removed);
+        let kept: Vec<i32> = (0..row.len()).filter(|i| !removed.contains(i)).map(|i| row[i]).collect();
+        assert!(safe_as_is(&kept), "{:?} without {:?} isn't safe", row, removed);
+    }
+
+    #[test]
+    fn the_example_reports() {
+        // how many levels the dampener removes from each, None if it can't make it safe
+        let reports = [
+            (vec![7, 6, 4, 2, 1], Some(0)),
+            (vec![1, 2, 7, 8, 9], None),
+            (vec![9, 7, 6, 2, 1], None),
+            (vec![1, 3, 2, 4, 5], Some(1)),
+            (vec![8, 6, 4, 4, 1], Some(1)),
+            (vec![1, 3, 6, 7, 9], Some(0)),
+        ];
+        let dampener = Dampener::new(1, SAFE_STEPS);
+        for (row, expected) in reports {
+            assert_eq!(dampener.removed_levels(&row).map(|removed| removed.len()), expected, "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn removes_the_first_level() {
+        assert_eq!(Dampener::new(1, SAFE_STEPS).removed_levels(&[9, 1, 2, 3, 4]), Some(vec![0]));
+        assert_eq!(Dampener::new(2, SAFE_STEPS).removed_levels(&[9, 9, 1, 2, 3]), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn removes_the_last_level() {
+        assert_eq!(Dampener::new(1, SAFE_STEPS).removed_levels(&[1, 2, 3, 4, 0]), Some(vec![4]));
+        assert_eq!(Dampener::new(2, SAFE_STEPS).removed_levels(&[5, 4, 3, 9, 9]), Some(vec![3, 4]));
+    }
+
+    #[test]
+    fn not_fixable_with_too_few_removals() {
+        let row = [1, 9, 2, 9, 3, 9, 4];
+        assert_eq!(Dampener::new(0, SAFE_STEPS).removed_levels(&row), None);
+        assert_eq!(Dampener::new(2, SAFE_STEPS).removed_levels(&row), None);
+        assert_eq!(Dampener::new(3, SAFE_STEPS).removed_levels(&row), Some(vec![1, 3, 5]));
+    }
+
+    #[test]
+    fn matches_brute_force_on_small_reports() {
+        // xorshift, so the same reports are checked every time
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+
+        for _ in 0..2000 {
+            let len = 1 + next(8) as usize;
+            let mut row = vec![next(10) as i32];
+            for _ in 1..len {
+                // mostly small steps either way, so plenty of the reports are nearly safe
+                let step = next(9) as i32 - 4;
+                row.push(row.last().unwrap() + step);
+            }
+            for max_removals in 0..=3 {
+                check_against_brute_force(&row, max_removals);
+            }
         }
     }
-    return false;
-}
\ No newline at end of file
+
+    fn verdict_for(levels: &[i32]) -> Verdict {
+        Verdict::new(1, levels.to_vec(), &Dampener::new(1, SAFE_STEPS))
+    }
+
+    // as the table shows it, after the report number and levels
+    fn table_columns(verdict: &Verdict) -> Vec<String> {
+        verdict.table_row()[2..].to_vec()
+    }
+
+    #[test]
+    fn unsafe_verdict() {
+        let verdict = verdict_for(&[1, 2, 7, 8, 9]);
+        assert_eq!(
+            verdict.to_json(),
+            "{\"report\": 1, \"levels\": [1, 2, 7, 8, 9], \"direction\": \"increasing\", \"status\": \"unsafe\", \
+             \"violation\": {\"index\": 2, \"reason\": \"step too large\"}, \"removed\": null}"
+        );
+        assert_eq!(table_columns(&verdict), ["increasing", "unsafe", "step too large at index 2", "-"]);
+    }
+
+    #[test]
+    fn dampened_verdicts() {
+        let verdict = verdict_for(&[1, 3, 2, 4, 5]);
+        assert_eq!(
+            verdict.to_json(),
+            "{\"report\": 1, \"levels\": [1, 3, 2, 4, 5], \"direction\": \"increasing\", \"status\": \"dampened\", \
+             \"violation\": {\"index\": 2, \"reason\": \"not monotonic\"}, \"removed\": [2]}"
+        );
+        assert_eq!(table_columns(&verdict), ["increasing", "dampened", "not monotonic at index 2", "index 2 (2)"]);
+
+        let verdict = verdict_for(&[8, 6, 4, 4, 1]);
+        assert_eq!(verdict.violation.map(|(index, reason)| (index, reason.name())), Some((3, "zero step")));
+        assert_eq!(table_columns(&verdict), ["decreasing", "dampened", "zero step at index 3", "index 3 (4)"]);
+    }
+
+    #[test]
+    fn safe_verdict() {
+        let verdict = verdict_for(&[7, 6, 4, 2, 1]);
+        assert!(verdict.to_json().ends_with("\"status\": \"safe\", \"violation\": null, \"removed\": []}"));
+        assert_eq!(table_columns(&verdict), ["decreasing", "safe", "-", "-"]);
+    }
+}
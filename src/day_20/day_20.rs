@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use crate::utils::input::input_path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Point {
@@ -230,7 +231,7 @@ impl Maze {
 
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = std::fs::read_to_string("src/day_20/input.txt").expect("Failed to read input file");
+    let input = std::fs::read_to_string(input_path("src/day_20/input.txt")).expect("Failed to read input file");
     
     let maze = Maze::from_input(&input);
     
@@ -247,15 +248,9 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let default_filename: &'static str = "./src/day_20/input.txt";
-    let args: Vec<String> = env::args().collect();
-    let in_file: String = match args.len() {
-        // No args provided save argv[0]
-        1 => String::from(default_filename),
-        _ => args[1].clone(),
-    };
+    let in_file = input_path("./src/day_20/input.txt");
 
-    let lines = fs::read_to_string(&in_file)
+    let lines = fs::read_to_string(in_file)
         .expect("Should have been able to read the file")
         .lines()
         .map(String::from)
@@ -275,7 +270,6 @@ pub fn run_b() -> std::io::Result<()> {
 
 }
 
-use std::env;
 use std::fs;
 
 // Advent of Code: 2024 day 20, part 2
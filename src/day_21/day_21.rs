@@ -1,7 +1,7 @@
 
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = include_str!("../../src/day_21/input.txt");
+    let input = &fs::read_to_string(input_path("src/day_21/input.txt"))?;
 
     // chain 3
     let result = solve(input, 2);
@@ -14,7 +14,7 @@ pub fn run_a() -> std::io::Result<()> {
 
 pub fn run_b() -> std::io::Result<()> {
     
-    let input = include_str!("../../src/day_21/input.txt");
+    let input = &fs::read_to_string(input_path("src/day_21/input.txt"))?;
 
     // chain 3
     let result = solve(input, 25);
@@ -28,6 +28,8 @@ pub fn run_b() -> std::io::Result<()> {
 use itertools::Itertools;
 use std::cell::LazyCell;
 use std::collections::HashMap;
+use std::fs;
+use crate::utils::input::input_path;
 
 /*
 +---+---+---+
@@ -1,43 +1,38 @@
-use crate::utils::read_lines_as_int_arrays::read_lines_as_int_arrays;
+use crate::utils::input::input_path;
+use crate::utils::stream_lines::{open_buffered, stream_numbers};
 use bitvec::prelude::*;
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_lines_as_int_arrays("src/day_22/input.txt", " ");
+    let reader = open_buffered(input_path("src/day_22/input.txt"))?;
 
-    // each line is just a single number, so put it into a big vec of i64
-    let starting_numbers = input.unwrap().into_iter().flatten().map(|x| x as u64).collect::<Vec<u64>>();
-
-    // print and collect the results
-    let mut final_secret_numbers = Vec::new();
-    for starting_number in starting_numbers {
-        let final_secret_number = apply_secret_number_rules_for_n_iterations(starting_number, 2000);
+    // each line is just a single number, read one at a time so the input can be as big as it likes
+    let mut sum: u64 = 0;
+    for starting_number in stream_numbers::<_, u64>(reader) {
+        let final_secret_number = apply_secret_number_rules_for_n_iterations(starting_number?, 2000);
         // println!("Final secret number for starting number {}: {}", starting_number, final_secret_number);
-        final_secret_numbers.push(final_secret_number);
+        sum += final_secret_number;
     }
 
-    // print sum of all final secret numbers
-    let sum: u64 = final_secret_numbers.iter().sum();
-
     println!("Sum of all final secret numbers: {}", sum);
 
     Ok(())
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_lines_as_int_arrays("src/day_22/input.txt", " ");
-
-    // each line is just a single number, so put it into a big vec of i64
-    let starting_numbers = input.unwrap().into_iter().flatten().map(|x| x as i64).collect::<Vec<i64>>();
+    let reader = open_buffered(input_path("src/day_22/input.txt"))?;
 
     // Map to store total bananas for each possible sequence of 4 deltas
     // Using a vector indexed by delta sequences instead of a HashMap for performance
-    let mut map = vec![0i16; 19usize.pow(4)];
+    // (u32 rather than i16 so the totals don't overflow with many thousands of buyers)
+    let mut map = vec![0u32; 19usize.pow(4)];
 
-    for &start in &starting_numbers {
+    // each line is just a single number, read one at a time as we go
+    for start in stream_numbers::<_, u64>(reader) {
+        let start = start?;
         // Track seen sequences for this monkey to avoid double-counting
         let mut seen = bitvec![0; 19usize.pow(4)];
         
-        let mut secret = start as u64;
+        let mut secret = start;
         let mut old_price = (secret % 10) as i8;
         let mut deltas = Vec::new();
 
@@ -72,7 +67,7 @@ pub fn run_b() -> std::io::Result<()> {
             }
 
             seen.set(idx, true);
-            map[idx] += price as i16;
+            map[idx] += price as u32;
         }
     }
 
@@ -2,6 +2,8 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
+use crate::utils::input::input_path;
+use crate::utils::repl::ReplSession;
 
 // Define our network structure
 struct Network {
@@ -84,6 +86,45 @@ impl Network {
         result
     }
 
+    // Find every fully connected set of n computers that includes the given one, without trying every combination
+    fn find_sets_containing(&self, computer: &str, n: usize) -> Vec<Vec<String>> {
+        let Some(neighbors) = self.connections.get(computer) else {
+            return Vec::new();
+        };
+        let mut neighbors: Vec<String> = neighbors.iter().cloned().collect();
+        neighbors.sort();
+
+        fn extend_set(
+            network: &Network,
+            current: &mut Vec<String>,
+            remaining: &[String],
+            n: usize,
+            result: &mut Vec<Vec<String>>,
+        ) {
+            if current.len() == n {
+                let mut sorted = current.clone();
+                sorted.sort();
+                result.push(sorted);
+                return;
+            }
+
+            for i in 0..remaining.len() {
+                if current.iter().all(|existing| network.connections[existing].contains(&remaining[i])) {
+                    current.push(remaining[i].clone());
+                    extend_set(network, current, &remaining[i + 1..], n, result);
+                    current.pop();
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut current = vec![computer.to_string()];
+        extend_set(self, &mut current, &neighbors, n, &mut result);
+
+        result.sort();
+        result
+    }
+
     // Find the largest set of fully connected computers
     fn find_largest_lan_party(&self) -> Vec<String> {
         let start_time = std::time::Instant::now();
@@ -160,8 +201,92 @@ fn parse_network<P: AsRef<Path>>(path: P) -> io::Result<Network> {
     Ok(network)
 }
 
+// the network from the input, for querying in the repl
+pub struct NetworkSession {
+    network: Network,
+}
+
+impl NetworkSession {
+    pub fn load() -> io::Result<Self> {
+        let network = parse_network(input_path("./src/day_23/input.txt"))?;
+        Ok(NetworkSession { network })
+    }
+
+    fn neighbors(&self, computer: &str) -> Result<Vec<&String>, String> {
+        let neighbors = self
+            .network
+            .connections
+            .get(computer)
+            .ok_or(format!("no computer called {}", computer))?;
+        let mut neighbors: Vec<&String> = neighbors.iter().collect();
+        neighbors.sort();
+        Ok(neighbors)
+    }
+}
+
+impl ReplSession for NetworkSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("nodes [prefix]", "list the computers, optionally only those starting with prefix"),
+            ("neighbors <computer>", "the computers directly connected to it"),
+            ("connected <a> <b>", "whether two computers are directly connected"),
+            ("cliques <computer> [size]", "the fully connected sets of size computers containing it, 3 by default"),
+            ("largest", "the largest fully connected set and its password"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "nodes" => {
+                let prefix = args.first().copied().unwrap_or("");
+                let mut computers: Vec<&String> = self
+                    .network
+                    .connections
+                    .keys()
+                    .filter(|computer| computer.starts_with(prefix))
+                    .collect();
+                computers.sort();
+                let names: Vec<&str> = computers.iter().map(|c| c.as_str()).collect();
+                Ok(format!("{} computers\n{}", names.len(), names.join(",")))
+            }
+            "neighbors" => {
+                let [computer] = args else {
+                    return Err("usage: neighbors <computer>".to_string());
+                };
+                let neighbors = self.neighbors(computer)?;
+                let names: Vec<&str> = neighbors.iter().map(|c| c.as_str()).collect();
+                Ok(format!("{} neighbors\n{}", names.len(), names.join(",")))
+            }
+            "connected" => {
+                let [a, b] = args else {
+                    return Err("usage: connected <a> <b>".to_string());
+                };
+                let connected = self.neighbors(a)?.iter().any(|neighbor| neighbor.as_str() == *b);
+                Ok(if connected { "yes" } else { "no" }.to_string())
+            }
+            "cliques" => {
+                let (computer, size) = match args {
+                    [computer] => (*computer, 3),
+                    [computer, size] => (*computer, size.parse().map_err(|_| format!("invalid size: {}", size))?),
+                    _ => return Err("usage: cliques <computer> [size]".to_string()),
+                };
+                self.neighbors(computer)?;
+
+                let sets = self.network.find_sets_containing(computer, size);
+                let lines: Vec<String> = sets.iter().map(|set| set.join(",")).collect();
+                Ok(format!("{} sets of {}\n{}", sets.len(), size, lines.join("\n")))
+            }
+            "largest" => {
+                let largest = self.network.find_largest_lan_party();
+                Ok(format!("{} computers, password: {}", largest.len(), largest.join(",")))
+            }
+            _ => Err(format!("unknown command: {}", command)),
+        }
+    }
+}
+
 pub fn run_a() -> std::io::Result<()> {
-    let network = parse_network("./src/day_23/input.txt")?;
+    let network = parse_network(input_path("./src/day_23/input.txt"))?;
 
     // find all sets of 3 interconnected computers
     let sets_of_3 = network.find_sets_of_n_computers(3);
@@ -179,7 +304,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let network = parse_network("./src/day_23/input.txt")?;
+    let network = parse_network(input_path("./src/day_23/input.txt"))?;
 
     // find the largest set of fully connected computers
     let largest_set = network.find_largest_lan_party();
@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
+use crate::utils::input::input_path;
 use crate::utils::read_lines::read_lines;
 use std::cmp::Ordering;
 
@@ -77,7 +78,7 @@ impl WireLogicGate {
 }
 
 pub fn run_a() -> Result<(), Box<dyn Error>> {
-    let (wires, wire_logic_gates) = parse_wire_input("src/day_24/input.txt")?;
+    let (wires, wire_logic_gates) = parse_wire_input(input_path("src/day_24/input.txt"))?;
     let result = simulate_system(&wires, &wire_logic_gates)?;
     println!("Decimal numbers of z wires: {}", result);
     Ok(())
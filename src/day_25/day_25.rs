@@ -1,6 +1,8 @@
-use crate::utils::read_as_columns::read_as_columns;
+use crate::utils::input::input_path;
+use crate::utils::transpose_into_columns::{transpose_aligned, TransposeOptions};
 
 use std::collections::HashSet;
+use std::fs;
 
 #[derive(Debug)]
 enum SchematicType {
@@ -28,29 +30,20 @@ impl Schematic {
             SchematicType::Key
         };
 
-        let width = lines[0].len();
-        let height = lines.len();
-        let mut heights = Vec::with_capacity(width);
-
-        // Process each column
-        for col in 0..width {
-            let column_height = match schematic_type {
-                SchematicType::Lock => {
-                    // For locks, count from top until we hit a '.'
-                    lines.iter()
-                        .position(|line| line.chars().nth(col) == Some('.'))
-                        .unwrap_or(height)
-                },
-                SchematicType::Key => {
-                    // For keys, count from bottom until we hit a '.'
-                    lines.iter()
-                        .rev()
-                        .position(|line| line.chars().nth(col) == Some('.'))
-                        .unwrap_or(height)
-                }
-            };
-            heights.push(column_height);
-        }
+        // a missing cell in a short row is just empty space
+        let options = TransposeOptions {
+            fill: '.',
+            ..Default::default()
+        };
+        let heights = transpose_aligned(&lines, &options)
+            .into_iter()
+            .map(|column| match schematic_type {
+                // For locks, count from top until we hit a '.'
+                SchematicType::Lock => column.cells.iter().position(|&c| c == '.').unwrap_or(column.cells.len()),
+                // For keys, count from bottom until we hit a '.'
+                SchematicType::Key => column.cells.iter().rev().position(|&c| c == '.').unwrap_or(column.cells.len()),
+            })
+            .collect();
 
         Some(Schematic {
             heights,
@@ -104,7 +97,7 @@ const TOTAL_HEIGHT: usize = 7;
 
 pub fn run_a() -> std::io::Result<()> {
     // read input
-    let input = include_str!("../../src/day_25/input.txt");
+    let input = &fs::read_to_string(input_path("src/day_25/input.txt"))?;
 
     // parse input
     let (locks, keys) = parse_input(input);
@@ -122,3 +115,28 @@ pub fn run_b() -> std::io::Result<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the heights count the solid top row of a lock and bottom row of a key, so they're one more than the puzzle's
+    #[test]
+    fn column_heights() {
+        let lock = Schematic::from_str("#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....").unwrap();
+        assert!(matches!(lock.schematic_type, SchematicType::Lock));
+        assert_eq!(lock.heights, vec![1, 6, 4, 5, 4]);
+
+        let key = Schematic::from_str(".....\n#....\n#....\n#...#\n#.#.#\n#.###\n#####").unwrap();
+        assert!(matches!(key.schematic_type, SchematicType::Key));
+        assert_eq!(key.heights, vec![6, 1, 3, 2, 4]);
+
+        assert!(check_overlap(&lock, &key, TOTAL_HEIGHT));
+    }
+
+    #[test]
+    fn short_rows_are_empty_space() {
+        let lock = Schematic::from_str("#####\n##\n#\n.\n.\n.\n.....").unwrap();
+        assert_eq!(lock.heights, vec![3, 2, 1, 1, 1]);
+    }
+}
@@ -1,19 +1,16 @@
-use crate::utils::read_as_string::read_as_string;
-use regex::Regex;
+use std::fs::File;
+use std::io::{Read, Write};
 
-pub fn run_a() -> std::io::Result<()> {
-    let input = read_as_string("src/day_3/input.txt")?;
-
-    // regex for all mult([0-9]+),([0-9]+) occurrences
-    let re = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
-    let mut result = 0;
-    for cap in re.captures_iter(&input) {
-        let a: i32 = cap[1].parse().unwrap();
-        let b: i32 = cap[2].parse().unwrap();
+use super::interpreter::Interpreter;
+use super::lexer::InstructionSet;
+use super::scanner::{Scanner, CHUNK_SIZE};
+use crate::utils::input::input_path;
+use crate::utils::read_as_string::LineEnding;
+use crate::utils::report::{format_table, ReportFormat};
 
-        // calculate the result as we go
-        result += a * b;
-    }
+pub fn run_a() -> std::io::Result<()> {
+    // every mul counts, do() and don't() are ignored
+    let result = run(false)?;
 
     println!("Sum of multiplications: {}", result);
     
@@ -21,48 +18,92 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let input = read_as_string("src/day_3/input.txt")?;
-    
-    // Find all command positions and their types
-    let re_do = Regex::new(r"do\(\)").unwrap();
-    let re_dont = Regex::new(r"don't\(\)").unwrap();
-    let re_mul = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
+    // mul only counts while it's been switched on by do(), or nothing has switched it off yet
+    let result = run(true)?;
 
-    // Create a vector of all command positions with their types
-    let mut commands: Vec<(usize, &str)> = vec![];
-    
-    // Add all commands to our vector with their positions
-    for m in re_do.find_iter(&input) {
-        commands.push((m.start(), "do"));
-    }
-    for m in re_dont.find_iter(&input) {
-        commands.push((m.start(), "dont"));
+    println!("Sum of multiplications between do() and don't(): {}", result);
+    Ok(())
+}
+
+// streams the memory dump rather than reading it all in, so its size doesn't matter. no instruction can
+// contain a line break, so the lines don't need any special handling
+fn run(conditionals: bool) -> std::io::Result<i64> {
+    let instructions = InstructionSet::standard();
+    let path = input_path("src/day_3/input.txt");
+    let mut scanner = Scanner::new(&instructions, File::open(path)?, CHUNK_SIZE);
+
+    let mut interpreter = Interpreter::new(conditionals);
+    for item in scanner.by_ref() {
+        let (token, _) = item?;
+        interpreter.execute(&token);
     }
-    for m in re_mul.find_iter(&input) {
-        commands.push((m.start(), "mul"));
+    note_line_endings(path, &scanner);
+    Ok(interpreter.total())
+}
+
+// the line endings make no difference to the instructions, this just makes it obvious a Windows-edited input was
+// read correctly
+fn note_line_endings<R: Read>(path: &str, scanner: &Scanner<R>) {
+    let line_ending = scanner.line_ending();
+    if matches!(line_ending, LineEnding::Crlf | LineEnding::Mixed) {
+        eprintln!("Note: {} has {:?} line endings", path, line_ending);
     }
-    
-    // Sort commands by position
-    commands.sort_by_key(|&(pos, _)| pos);
+}
 
-    let mut result = 0;
-    let mut mul_active = true;  // Start with mul active
+// every instruction found with where it is and what it added, as run for part B, for auditing the total. the JSON is
+// written as the instructions are found so it doesn't hold a whole dump's worth, the table needs every row to line
+// up the columns
+pub fn report(format: ReportFormat, out: &mut dyn Write) -> std::io::Result<()> {
+    let instructions = InstructionSet::standard();
+    let path = input_path("src/day_3/input.txt");
+    let mut scanner = Scanner::new(&instructions, File::open(path)?, CHUNK_SIZE);
+    let mut interpreter = Interpreter::new(true);
 
-    for (pos, cmd_type) in commands {
-        match cmd_type {
-            "do" => mul_active = true,
-            "dont" => mul_active = false,
-            "mul" if mul_active => {
-                if let Some(cap) = re_mul.captures(&input[pos..]) {
-                    let a: i32 = cap[1].parse().unwrap();
-                    let b: i32 = cap[2].parse().unwrap();
-                    result += a * b;
-                }
+    match format {
+        ReportFormat::Json => {
+            write!(out, "[")?;
+            for (i, item) in scanner.by_ref().enumerate() {
+                let (token, location) = item?;
+                let step = interpreter.execute(&token);
+                let args: Vec<String> = token.args.iter().map(|arg| arg.to_string()).collect();
+                write!(
+                    out,
+                    "{}  {{\"offset\": {}, \"line\": {}, \"column\": {}, \"instruction\": \"{}\", \"args\": [{}], \"enabled\": {}, \"contribution\": {}}}",
+                    if i == 0 { "\n" } else { ",\n" },
+                    token.offset,
+                    location.line,
+                    location.column,
+                    token.spec.name.replace('\\', "\\\\").replace('"', "\\\""),
+                    args.join(", "),
+                    step.enabled,
+                    step.contribution
+                )?;
+            }
+            writeln!(out, "\n]")?;
+        }
+        ReportFormat::Table => {
+            let mut table = vec![["Offset", "Line", "Column", "Instruction", "Args", "Enabled", "Contribution"]
+                .iter()
+                .map(|header| header.to_string())
+                .collect::<Vec<String>>()];
+            for item in scanner.by_ref() {
+                let (token, location) = item?;
+                let step = interpreter.execute(&token);
+                let args: Vec<String> = token.args.iter().map(|arg| arg.to_string()).collect();
+                table.push(vec![
+                    token.offset.to_string(),
+                    location.line.to_string(),
+                    location.column.to_string(),
+                    token.spec.name.to_string(),
+                    args.join(","),
+                    if step.enabled { "yes" } else { "no" }.to_string(),
+                    step.contribution.to_string(),
+                ]);
             }
-            _ => {}
+            writeln!(out, "{}Total: {}", format_table(&table), interpreter.total())?;
         }
     }
+    note_line_endings(path, &scanner);
 
-    println!("Sum of multiplications between do() and don't(): {}", result);
     Ok(())
 }
@@ -0,0 +1,52 @@
+use super::lexer::{Op, Token};
+
+// Runs the instructions the lexer finds, keeping the running total and whether do()/don't() have
+// switched the value instructions off
+
+pub struct Interpreter {
+    // part A ignores do() and don't()
+    conditionals: bool,
+    enabled: bool,
+    total: i64,
+}
+
+// what running one instruction did
+pub struct Step {
+    // whether value instructions were on when it ran
+    pub enabled: bool,
+    // how much it added to the total
+    pub contribution: i64,
+}
+
+impl Interpreter {
+    pub fn new(conditionals: bool) -> Self {
+        Interpreter {
+            conditionals,
+            enabled: true,
+            total: 0,
+        }
+    }
+
+    pub fn execute(&mut self, token: &Token) -> Step {
+        let enabled = self.enabled;
+        let contribution = match token.spec.op {
+            Op::Enable => {
+                self.enabled = true;
+                0
+            }
+            Op::Disable => {
+                self.enabled = !self.conditionals;
+                0
+            }
+            Op::Value(value) if enabled => value(&token.args),
+            Op::Value(_) => 0,
+        };
+
+        self.total += contribution;
+        Step { enabled, contribution }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+}
\ No newline at end of file
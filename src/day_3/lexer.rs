@@ -0,0 +1,249 @@
+// Finds the instructions hidden in the corrupted memory in one pass over the bytes. An instruction is its name,
+// then its arguments in brackets, separated by commas, e.g. mul(12,345) or do(). Anything that doesn't fit
+// exactly is just skipped over
+
+// what an instruction does when it's run
+#[derive(Clone, Copy)]
+pub enum Op {
+    Enable,
+    Disable,
+    // the value it adds to the total
+    Value(fn(&[i64]) -> i64),
+}
+
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub args: usize,
+    // each argument is 1 to max_digits digits, and one too big for an i64 doesn't match
+    pub max_digits: usize,
+    pub op: Op,
+}
+
+pub struct InstructionSet {
+    specs: Vec<InstructionSpec>,
+    // whether an instruction name starts with each byte, so most positions are skipped with one lookup
+    first_bytes: [bool; 256],
+}
+
+impl InstructionSet {
+    pub fn new() -> Self {
+        InstructionSet {
+            specs: Vec::new(),
+            first_bytes: [false; 256],
+        }
+    }
+
+    // mul, do and don't, as in the puzzle
+    pub fn standard() -> Self {
+        InstructionSet::new()
+            .register(InstructionSpec {
+                name: "mul",
+                args: 2,
+                max_digits: 3,
+                op: Op::Value(|args| args[0] * args[1]),
+            })
+            .register(InstructionSpec {
+                name: "do",
+                args: 0,
+                max_digits: 0,
+                op: Op::Enable,
+            })
+            .register(InstructionSpec {
+                name: "don't",
+                args: 0,
+                max_digits: 0,
+                op: Op::Disable,
+            })
+    }
+
+    // the most bytes any instruction can take up
+    pub fn max_len(&self) -> usize {
+        self.specs
+            .iter()
+            .map(|spec| {
+                let args = spec.args.saturating_mul(spec.max_digits).saturating_add(spec.args.saturating_sub(1));
+                (spec.name.len() + 2).saturating_add(args)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn register(mut self, spec: InstructionSpec) -> Self {
+        if let Some(&first) = spec.name.as_bytes().first() {
+            self.first_bytes[first as usize] = true;
+        }
+        self.specs.push(spec);
+        self
+    }
+
+    // the first instruction starting at or after from and before until, which may run on past until
+    pub fn find_next<'a>(&'a self, input: &[u8], from: usize, until: usize) -> Option<Token<'a>> {
+        let until = until.min(input.len());
+        (from..until)
+            .filter(|&position| self.first_bytes[input[position] as usize])
+            .find_map(|position| self.match_at(input, position))
+    }
+
+    fn match_at<'a>(&'a self, input: &[u8], position: usize) -> Option<Token<'a>> {
+        self.specs.iter().find_map(|spec| {
+            let (args, len) = match_instruction(spec, &input[position..])?;
+            Some(Token {
+                spec,
+                args,
+                offset: position,
+                len,
+            })
+        })
+    }
+}
+
+impl Default for InstructionSet {
+    fn default() -> Self {
+        InstructionSet::new()
+    }
+}
+
+pub struct Token<'a> {
+    pub spec: &'a InstructionSpec,
+    pub args: Vec<i64>,
+    // where the instruction starts in the input, and how many bytes it takes up
+    pub offset: usize,
+    pub len: usize,
+}
+
+// the arguments and length of the instruction at the start of input, if it's there and well formed
+fn match_instruction(spec: &InstructionSpec, input: &[u8]) -> Option<(Vec<i64>, usize)> {
+    let mut position = spec.name.len();
+    if !input.starts_with(spec.name.as_bytes()) || input.get(position) != Some(&b'(') {
+        return None;
+    }
+    position += 1;
+
+    let mut args = Vec::with_capacity(spec.args);
+    for i in 0..spec.args {
+        if i > 0 {
+            if input.get(position) != Some(&b',') {
+                return None;
+            }
+            position += 1;
+        }
+
+        let digits = input[position..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 || digits > spec.max_digits {
+            return None;
+        }
+        let value = input[position..position + digits]
+            .iter()
+            .try_fold(0i64, |value, &digit| value.checked_mul(10)?.checked_add((digit - b'0') as i64))?;
+        args.push(value);
+        position += digits;
+    }
+
+    if input.get(position) != Some(&b')') {
+        return None;
+    }
+    Some((args, position + 1))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_all<'a>(set: &'a InstructionSet, input: &str) -> Vec<Token<'a>> {
+        let input = input.as_bytes();
+        let mut tokens = Vec::new();
+        let mut from = 0;
+        while let Some(token) = set.find_next(input, from, input.len()) {
+            from = token.offset + token.len;
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    // each token as its name and arguments
+    fn names_and_args(set: &InstructionSet, input: &str) -> Vec<(&'static str, Vec<i64>)> {
+        find_all(set, input).into_iter().map(|token| (token.spec.name, token.args)).collect()
+    }
+
+    #[test]
+    fn finds_mul() {
+        let set = InstructionSet::standard();
+        let tokens = find_all(&set, "xmul(1,2)");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!((tokens[0].spec.name, tokens[0].args.clone()), ("mul", vec![1, 2]));
+        assert_eq!((tokens[0].offset, tokens[0].len), (1, 8));
+        assert_eq!(names_and_args(&set, "mul(123,456)"), vec![("mul", vec![123, 456])]);
+    }
+
+    #[test]
+    fn rejects_malformed_mul() {
+        let set = InstructionSet::standard();
+        // too many digits, spaces, the wrong brackets and a missing argument
+        let inputs = ["mul(1234,5)", "mul(5,1234)", "mul(1, 2)", "mul (1,2)", "mul[1,2]", "mul(1)", "mul(,2)", "mul(1,2,3)"];
+        for input in inputs {
+            assert!(find_all(&set, input).is_empty(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn unfinished_mul_at_the_end() {
+        let set = InstructionSet::standard();
+        for input in ["mul(1,2", "mul(1,", "mul(", "mul"] {
+            assert!(find_all(&set, input).is_empty(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn tells_do_and_dont_apart() {
+        let set = InstructionSet::standard();
+        let ops: Vec<&str> = find_all(&set, "don't()do()don't(do()")
+            .iter()
+            .map(|token| token.spec.name)
+            .collect();
+        assert_eq!(ops, vec!["don't", "do", "do"]);
+        assert!(matches!(find_all(&set, "don't()")[0].spec.op, Op::Disable));
+        assert!(matches!(find_all(&set, "do()")[0].spec.op, Op::Enable));
+    }
+
+    #[test]
+    fn registered_instructions() {
+        let set = InstructionSet::standard()
+            .register(InstructionSpec {
+                name: "add",
+                args: 2,
+                max_digits: 3,
+                op: Op::Value(|args| args[0] + args[1]),
+            })
+            .register(InstructionSpec {
+                name: "sub",
+                args: 2,
+                max_digits: 3,
+                op: Op::Value(|args| args[0] - args[1]),
+            });
+
+        let tokens = find_all(&set, "add(1,2)sub(10,4)mul(2,3)sub(1)");
+        let values: Vec<(&str, i64)> = tokens
+            .iter()
+            .map(|token| match token.spec.op {
+                Op::Value(value) => (token.spec.name, value(&token.args)),
+                _ => (token.spec.name, 0),
+            })
+            .collect();
+        assert_eq!(values, vec![("add", 3), ("sub", 6), ("mul", 6)]);
+        assert_eq!(set.max_len(), "mul(123,456)".len());
+    }
+
+    #[test]
+    fn arguments_too_big_for_an_i64_dont_match() {
+        let set = InstructionSet::new().register(InstructionSpec {
+            name: "big",
+            args: 1,
+            max_digits: 30,
+            op: Op::Value(|args| args[0]),
+        });
+
+        assert_eq!(names_and_args(&set, "big(9223372036854775807)"), vec![("big", vec![i64::MAX])]);
+        assert!(find_all(&set, "big(9223372036854775808)").is_empty());
+        assert!(find_all(&set, "big(999999999999999999999999999999)").is_empty());
+        assert_eq!(set.max_len(), "big()".len() + 30);
+    }
+}
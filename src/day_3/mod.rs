@@ -1 +1,4 @@
 pub mod day_3;
+mod interpreter;
+mod lexer;
+mod scanner;
\ No newline at end of file
@@ -0,0 +1,250 @@
+use std::io::{self, Read};
+
+use super::lexer::{InstructionSet, Token};
+use crate::utils::read_as_string::LineEnding;
+
+// Finds the instructions in a stream a chunk at a time, so a dump of any size is scanned in the memory of one
+// chunk. The last max_len - 1 bytes of each chunk are carried over to the next, since an instruction starting
+// there could be cut off, and only scanned once there's enough after them or the stream has ended
+
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+// where an instruction is, both counted from 1. the column is in bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+pub struct Scanner<'a, R: Read> {
+    set: &'a InstructionSet,
+    reader: R,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    // where buffer[0] is in the whole stream
+    base: usize,
+    // the next position in the buffer an instruction could start at
+    position: usize,
+    finished: bool,
+    // the line breaks have been counted up to here in the buffer
+    counted: usize,
+    line: usize,
+    // where the current line starts in the whole stream
+    line_start: usize,
+    // the last byte counted, as a \r before a \n can be at the end of the previous chunk
+    previous: Option<u8>,
+    lf_count: usize,
+    crlf_count: usize,
+}
+
+impl<'a, R: Read> Scanner<'a, R> {
+    pub fn new(set: &'a InstructionSet, reader: R, chunk_size: usize) -> Self {
+        Scanner {
+            set,
+            reader,
+            chunk_size: chunk_size.max(1),
+            buffer: Vec::new(),
+            base: 0,
+            position: 0,
+            finished: false,
+            counted: 0,
+            line: 1,
+            line_start: 0,
+            previous: None,
+            lf_count: 0,
+            crlf_count: 0,
+        }
+    }
+
+    // the line endings seen so far, which is all of them once the scan has finished
+    pub fn line_ending(&self) -> LineEnding {
+        LineEnding::from_counts(self.lf_count, self.crlf_count)
+    }
+
+    fn count_lines(&mut self, until: usize) {
+        for (i, &byte) in self.buffer[self.counted..until].iter().enumerate() {
+            if byte == b'\n' {
+                if self.previous == Some(b'\r') {
+                    self.crlf_count += 1;
+                } else {
+                    self.lf_count += 1;
+                }
+                self.line += 1;
+                self.line_start = self.base + self.counted + i + 1;
+            }
+            self.previous = Some(byte);
+        }
+        self.counted = until;
+    }
+
+    // drops everything already scanned and appends the next chunk, returning false at the end of the stream
+    fn read_chunk(&mut self) -> io::Result<bool> {
+        self.count_lines(self.position);
+        self.buffer.drain(..self.position);
+        self.base += self.position;
+        self.position = 0;
+        self.counted = 0;
+
+        let start = self.buffer.len();
+        self.buffer.resize(start + self.chunk_size, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buffer[start..]) {
+                Ok(read) => break read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.buffer.truncate(start);
+                    return Err(e);
+                }
+            }
+        };
+        self.buffer.truncate(start + read);
+        Ok(read > 0)
+    }
+}
+
+impl<'a, R: Read> Iterator for Scanner<'a, R> {
+    // the token's offset is from the start of the stream
+    type Item = io::Result<(Token<'a>, Location)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // an instruction starting before here fits in the buffer if it's there at all
+            let limit = if self.finished {
+                self.buffer.len()
+            } else {
+                self.buffer.len().saturating_sub(self.set.max_len().saturating_sub(1))
+            };
+
+            if let Some(mut token) = self.set.find_next(&self.buffer, self.position, limit) {
+                self.count_lines(token.offset);
+                self.position = token.offset + token.len;
+                token.offset += self.base;
+
+                let location = Location {
+                    line: self.line,
+                    column: token.offset - self.line_start + 1,
+                };
+                return Some(Ok((token, location)));
+            }
+            self.position = self.position.max(limit);
+
+            if self.finished {
+                self.count_lines(self.buffer.len());
+                return None;
+            }
+            match self.read_chunk() {
+                Ok(more) => self.finished = !more,
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_3::interpreter::Interpreter;
+
+    const INSTRUCTIONS: [&str; 3] = ["mul(123,4)", "don't()", "do()"];
+
+    // each token as its name, arguments, offset and whether it was enabled, as run for part B
+    fn run_tokens(tokens: Vec<Token>) -> Vec<(&'static str, Vec<i64>, usize, bool)> {
+        let mut interpreter = Interpreter::new(true);
+        tokens
+            .into_iter()
+            .map(|token| {
+                let step = interpreter.execute(&token);
+                (token.spec.name, token.args, token.offset, step.enabled)
+            })
+            .collect()
+    }
+
+    fn whole_buffer(set: &InstructionSet, input: &[u8]) -> Vec<(&'static str, Vec<i64>, usize, bool)> {
+        let mut tokens = Vec::new();
+        let mut from = 0;
+        while let Some(token) = set.find_next(input, from, input.len()) {
+            from = token.offset + token.len;
+            tokens.push(token);
+        }
+        run_tokens(tokens)
+    }
+
+    fn scanned(set: &InstructionSet, input: &[u8], chunk_size: usize) -> Vec<(&'static str, Vec<i64>, usize, bool)> {
+        let tokens = Scanner::new(set, input, chunk_size)
+            .map(|item| item.map(|(token, _)| token))
+            .collect::<io::Result<Vec<Token>>>()
+            .unwrap();
+        run_tokens(tokens)
+    }
+
+    #[test]
+    fn instructions_split_at_every_offset() {
+        let set = InstructionSet::standard();
+        for chunk_size in 1..=set.max_len() {
+            for instruction in INSTRUCTIONS {
+                // the padding moves the chunk boundary through every offset in the instruction
+                for padding in 0..chunk_size {
+                    let input = format!("{}{}xmul(2,3)", "x".repeat(padding), instruction);
+                    let expected = whole_buffer(&set, input.as_bytes());
+                    assert_eq!(expected.len(), 2, "{:?}", input);
+                    let tokens = scanned(&set, input.as_bytes(), chunk_size);
+                    assert_eq!(tokens, expected, "{:?} in chunks of {}", input, chunk_size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn enabled_state_carries_across_chunks() {
+        let set = InstructionSet::standard();
+        let input = "mul(123,4)don't()mul(123,4)?do()mul(123,4)don't()do()mul(1,1)";
+        let expected = whole_buffer(&set, input.as_bytes());
+        let enabled: Vec<bool> = expected.iter().filter(|(name, ..)| *name == "mul").map(|token| token.3).collect();
+        assert_eq!(enabled, vec![true, false, true, true]);
+
+        for chunk_size in 1..=set.max_len() {
+            assert_eq!(scanned(&set, input.as_bytes(), chunk_size), expected, "chunks of {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn counts_line_endings_split_across_chunks() {
+        let set = InstructionSet::standard();
+        for (input, line_ending) in [
+            ("mul(1,2)mul(3,4)", LineEnding::None),
+            ("mul(1,2)\nmul(3,4)\n", LineEnding::Lf),
+            ("mul(1,2)\r\nmul(3,4)\r\n", LineEnding::Crlf),
+            ("mul(1,2)\r\nmul(3,4)\n", LineEnding::Mixed),
+        ] {
+            for chunk_size in 1..=set.max_len() {
+                let mut scanner = Scanner::new(&set, input.as_bytes(), chunk_size);
+                assert_eq!(scanner.by_ref().count(), 2);
+                assert_eq!(scanner.line_ending(), line_ending, "{:?} in chunks of {}", input, chunk_size);
+            }
+        }
+    }
+
+    #[test]
+    fn locations_across_chunk_boundaries() {
+        let set = InstructionSet::standard();
+        let input = "mul(1,2)\n\nxx do()mul(3,4)\r\n\n\n  don't()\nmul(5,6)";
+        let expected = vec![
+            Location { line: 1, column: 1 },
+            Location { line: 3, column: 4 },
+            Location { line: 3, column: 8 },
+            Location { line: 6, column: 3 },
+            Location { line: 7, column: 1 },
+        ];
+
+        for chunk_size in 1..=set.max_len() {
+            let locations = Scanner::new(&set, input.as_bytes(), chunk_size)
+                .map(|item| item.map(|(_, location)| location))
+                .collect::<io::Result<Vec<Location>>>()
+                .unwrap();
+            assert_eq!(locations, expected, "chunks of {}", chunk_size);
+        }
+    }
+}
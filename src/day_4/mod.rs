@@ -1 +1,3 @@
 pub mod day_4;
+mod template;
+mod word_search;
\ No newline at end of file
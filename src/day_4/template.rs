@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+// A small 2D shape to look for in the grid, where some cells must be a given letter and the rest
+// (the wildcards) can be anything, e.g. the X-MAS cross
+//
+//     M.S
+//     .A.
+//     M.S
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Template {
+    // None is a wildcard
+    cells: Vec<Vec<Option<char>>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemplateMatch {
+    // the top left corner of the match
+    pub row: usize,
+    pub column: usize,
+    // which of the variants matched, 0 being the template as written
+    pub variant: usize,
+}
+
+impl Template {
+    // shorter rows are padded with wildcards
+    pub fn parse<S: AsRef<str>>(rows: &[S], wildcard: char) -> Self {
+        let width = rows.iter().map(|row| row.as_ref().chars().count()).max().unwrap_or(0);
+        let cells = rows
+            .iter()
+            .map(|row| {
+                let mut cells: Vec<Option<char>> = row.as_ref().chars().map(|c| (c != wildcard).then_some(c)).collect();
+                cells.resize(width, None);
+                cells
+            })
+            .collect();
+        Template { cells }
+    }
+
+    fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn width(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    // a quarter turn clockwise
+    fn rotated(&self) -> Template {
+        let cells = (0..self.width())
+            .map(|column| (0..self.height()).rev().map(|row| self.cells[row][column]).collect())
+            .collect();
+        Template { cells }
+    }
+
+    // mirrored left to right
+    fn reflected(&self) -> Template {
+        let cells = self.cells.iter().map(|row| row.iter().rev().copied().collect()).collect();
+        Template { cells }
+    }
+
+    // every rotation and reflection of the template, without the ones that come out the same, starting with
+    // the template itself
+    pub fn variants(&self) -> Vec<Template> {
+        let mut seen = HashSet::new();
+        let mut variants = Vec::new();
+
+        for start in [self.clone(), self.reflected()] {
+            let mut variant = start;
+            for _ in 0..4 {
+                let next = variant.rotated();
+                if seen.insert(variant.clone()) {
+                    variants.push(variant);
+                }
+                variant = next;
+            }
+        }
+
+        variants
+    }
+
+    fn matches_at(&self, grid: &[Vec<char>], row: usize, column: usize) -> bool {
+        self.cells.iter().enumerate().all(|(r, cells)| {
+            cells.iter().enumerate().all(|(c, cell)| match cell {
+                Some(expected) => grid.get(row + r).and_then(|line| line.get(column + c)) == Some(expected),
+                None => true,
+            })
+        })
+    }
+
+    // every place the template fits, or any of its variants when all_variants is set. an empty template has
+    // nothing to match, so it's found nowhere
+    pub fn find(&self, grid: &[Vec<char>], all_variants: bool) -> Vec<TemplateMatch> {
+        let mut matches = Vec::new();
+        if self.height() == 0 || self.width() == 0 {
+            return matches;
+        }
+        let templates = if all_variants { self.variants() } else { vec![self.clone()] };
+
+        for (variant, template) in templates.iter().enumerate() {
+            if template.height() > grid.len() {
+                continue;
+            }
+            for row in 0..=grid.len() - template.height() {
+                let columns = grid[row].len();
+                if template.width() > columns {
+                    continue;
+                }
+                for column in 0..=columns - template.width() {
+                    if template.matches_at(grid, row, column) {
+                        matches.push(TemplateMatch { row, column, variant });
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Vec<Vec<char>> {
+        text.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    fn x_mas() -> Template {
+        Template::parse(&["M.S", ".A.", "M.S"], '.')
+    }
+
+    #[test]
+    fn x_mas_has_four_variants() {
+        let variants = x_mas().variants();
+        assert_eq!(variants.len(), 4);
+        assert_eq!(variants[0], x_mas());
+    }
+
+    #[test]
+    fn symmetric_template_has_one_variant() {
+        let ring = Template::parse(&["A.A", ".A.", "A.A"], '.');
+        assert_eq!(ring.variants(), vec![ring]);
+    }
+
+    #[test]
+    fn finds_x_mas_in_the_example() {
+        let grid = grid(include_str!("input_example.txt"));
+        assert_eq!(x_mas().find(&grid, true).len(), 9);
+    }
+
+    #[test]
+    fn empty_template_matches_nowhere() {
+        let grid = grid("MAS\nAMS");
+        let no_rows: [&str; 0] = [];
+        assert!(Template::parse(&no_rows, '.').find(&grid, true).is_empty());
+        assert!(Template::parse(&["", ""], '.').find(&grid, true).is_empty());
+        assert!(x_mas().find(&[], true).is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::{HashMap, VecDeque};
+
+// Finds any number of words in a grid of letters at once. Every line through the grid in each direction
+// is read through an Aho-Corasick automaton built from all the words, so the grid is only read once per
+// direction however many words there are
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Directions {
+    // right, down, left and up
+    Orthogonal,
+    Diagonal,
+    All,
+}
+
+impl Directions {
+    // the set a (row, column) step belongs to, other than All
+    pub fn of(step: (i64, i64)) -> Directions {
+        if step.0 == 0 || step.1 == 0 {
+            Directions::Orthogonal
+        } else {
+            Directions::Diagonal
+        }
+    }
+
+    // as (row, column) steps
+    fn steps(&self) -> &'static [(i64, i64)] {
+        const ORTHOGONAL: [(i64, i64); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+        const DIAGONAL: [(i64, i64); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+        const ALL: [(i64, i64); 8] = [(0, 1), (1, 0), (1, 1), (-1, 1), (0, -1), (-1, 0), (-1, -1), (1, -1)];
+
+        match self {
+            Directions::Orthogonal => &ORTHOGONAL,
+            Directions::Diagonal => &DIAGONAL,
+            Directions::All => &ALL,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions {
+    pub directions: Directions,
+    // words can run off one edge and carry on from the opposite one
+    pub wrap: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    // the index of the word in the list it was searched for with
+    pub word: usize,
+    pub row: usize,
+    pub column: usize,
+    // the (row, column) step from each letter to the next
+    pub direction: (i64, i64),
+}
+
+struct Node {
+    next: HashMap<char, usize>,
+    fail: usize,
+    // the words that end here, including those ending at any node down the fail links
+    words: Vec<usize>,
+}
+
+pub struct WordSearch {
+    lengths: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+impl WordSearch {
+    pub fn new<S: AsRef<str>>(words: &[S]) -> Self {
+        let mut search = WordSearch {
+            lengths: Vec::with_capacity(words.len()),
+            nodes: vec![Node {
+                next: HashMap::new(),
+                fail: 0,
+                words: Vec::new(),
+            }],
+        };
+
+        for (index, word) in words.iter().enumerate() {
+            let mut node = 0;
+            for c in word.as_ref().chars() {
+                node = match search.nodes[node].next.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        search.nodes.push(Node {
+                            next: HashMap::new(),
+                            fail: 0,
+                            words: Vec::new(),
+                        });
+                        let next = search.nodes.len() - 1;
+                        search.nodes[node].next.insert(c, next);
+                        next
+                    }
+                };
+            }
+            // an empty word would match everywhere, so it never matches instead
+            if node != 0 {
+                search.nodes[node].words.push(index);
+            }
+            search.lengths.push(word.as_ref().chars().count());
+        }
+
+        search.build_fail_links();
+        search
+    }
+
+    // breadth first, so a node's fail link is always finished before its children's
+    fn build_fail_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[0].next.values().copied().collect();
+
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(char, usize)> = self.nodes[node].next.iter().map(|(&c, &child)| (c, child)).collect();
+            for (c, child) in children {
+                let mut fail = self.nodes[node].fail;
+                while fail != 0 && !self.nodes[fail].next.contains_key(&c) {
+                    fail = self.nodes[fail].fail;
+                }
+                let fail = self.nodes[fail].next.get(&c).copied().filter(|&next| next != child).unwrap_or(0);
+
+                self.nodes[child].fail = fail;
+                let inherited = self.nodes[fail].words.clone();
+                self.nodes[child].words.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn step(&self, mut node: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&c) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    pub fn find(&self, grid: &[Vec<char>], options: &SearchOptions) -> Vec<Match> {
+        let longest = self.lengths.iter().copied().max().unwrap_or(0);
+        let mut matches = Vec::new();
+        if grid.is_empty() || grid[0].is_empty() || longest == 0 {
+            return matches;
+        }
+
+        for &direction in options.directions.steps() {
+            let lines = if options.wrap {
+                wrapped_lines(grid.len(), grid[0].len(), direction)
+            } else {
+                straight_lines(grid.len(), grid[0].len(), direction)
+            };
+
+            for line in lines {
+                // a wrapped line is a loop, so it's read around again far enough for a word to start at its end
+                let read = if options.wrap { line.len() + longest - 1 } else { line.len() };
+
+                let mut node = 0;
+                for i in 0..read {
+                    let (row, column) = line[i % line.len()];
+                    node = self.step(node, grid[row][column]);
+
+                    for &word in &self.nodes[node].words {
+                        let length = self.lengths[word];
+                        // a loop shorter than the word would have to use some letters twice
+                        if length > i + 1 || length > line.len() {
+                            continue;
+                        }
+                        let start = i + 1 - length;
+                        if start >= line.len() {
+                            // already found on the first time round
+                            continue;
+                        }
+                        let (row, column) = line[start];
+                        matches.push(Match {
+                            word,
+                            row,
+                            column,
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+// every line across the grid in the direction, from the edge it enters on to the edge it leaves by
+fn straight_lines(rows: usize, columns: usize, (dr, dc): (i64, i64)) -> Vec<Vec<(usize, usize)>> {
+    let inside = |r: i64, c: i64| r >= 0 && r < rows as i64 && c >= 0 && c < columns as i64;
+    let mut lines = Vec::new();
+
+    for row in 0..rows as i64 {
+        for column in 0..columns as i64 {
+            // only start where the step before would be outside the grid
+            if inside(row - dr, column - dc) {
+                continue;
+            }
+            let mut line = Vec::new();
+            let (mut r, mut c) = (row, column);
+            while inside(r, c) {
+                line.push((r as usize, c as usize));
+                r += dr;
+                c += dc;
+            }
+            lines.push(line);
+        }
+    }
+
+    lines
+}
+
+// on a grid that wraps round, every line in the direction is a loop that comes back to where it started
+fn wrapped_lines(rows: usize, columns: usize, (dr, dc): (i64, i64)) -> Vec<Vec<(usize, usize)>> {
+    let mut visited = vec![vec![false; columns]; rows];
+    let mut lines = Vec::new();
+
+    for row in 0..rows {
+        for column in 0..columns {
+            if visited[row][column] {
+                continue;
+            }
+            let mut line = Vec::new();
+            let (mut r, mut c) = (row, column);
+            while !visited[r][c] {
+                visited[r][c] = true;
+                line.push((r, c));
+                r = (r as i64 + dr).rem_euclid(rows as i64) as usize;
+                c = (c as i64 + dc).rem_euclid(columns as i64) as usize;
+            }
+            lines.push(line);
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    fn options(directions: Directions, wrap: bool) -> SearchOptions {
+        SearchOptions { directions, wrap }
+    }
+
+    // each match as (word, column), reading left to right along a single row
+    fn found_in_row<S: AsRef<str>>(words: &[S], row: &str) -> Vec<(usize, usize)> {
+        let mut found: Vec<(usize, usize)> = WordSearch::new(words)
+            .find(&grid(&[row]), &options(Directions::Orthogonal, false))
+            .into_iter()
+            .filter(|found| found.direction == (0, 1))
+            .map(|found| (found.word, found.column))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn words_sharing_prefixes_and_suffixes() {
+        // he is only found inside she and hers through the fail links
+        let words = ["he", "she", "his", "hers"];
+        assert_eq!(found_in_row(&words, "ushers"), vec![(0, 2), (1, 1), (3, 2)]);
+        assert_eq!(found_in_row(&words, "ahishe"), vec![(0, 4), (1, 3), (2, 1)]);
+
+        // one word the start of another, and one the end of another
+        let words = ["ab", "abc", "bc", "c"];
+        assert_eq!(found_in_row(&words, "abcab"), vec![(0, 0), (0, 3), (1, 0), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn each_direction_set() {
+        let grid = grid(&["BBB", "BAB", "BBB"]);
+        let search = WordSearch::new(&["AB"]);
+        let directions = |set| {
+            let found = search.find(&grid, &options(set, false));
+            let mut found: Vec<(i64, i64)> = found.iter().map(|found| found.direction).collect();
+            found.sort();
+            found
+        };
+
+        let mut orthogonal = vec![(0, 1), (1, 0), (0, -1), (-1, 0)];
+        let mut diagonal = vec![(1, 1), (-1, 1), (-1, -1), (1, -1)];
+        orthogonal.sort();
+        diagonal.sort();
+        let mut all = [orthogonal.clone(), diagonal.clone()].concat();
+        all.sort();
+
+        assert_eq!(directions(Directions::Orthogonal), orthogonal);
+        assert_eq!(directions(Directions::Diagonal), diagonal);
+        assert_eq!(directions(Directions::All), all);
+
+        assert!(orthogonal.iter().all(|&step| Directions::of(step) == Directions::Orthogonal));
+        assert!(diagonal.iter().all(|&step| Directions::of(step) == Directions::Diagonal));
+    }
+
+    #[test]
+    fn wrap_carries_on_from_the_opposite_edge() {
+        let grid = grid(&["XYZ", "ABC"]);
+        let search = WordSearch::new(&["CAB", "YBY"]);
+
+        assert!(search.find(&grid, &options(Directions::All, false)).is_empty());
+        let found = search.find(&grid, &options(Directions::Orthogonal, true));
+        assert_eq!(found, vec![Match { word: 0, row: 1, column: 2, direction: (0, 1) }]);
+    }
+
+    #[test]
+    fn wrap_never_reuses_a_letter() {
+        // every row loops round in 2 letters and every column in 3, too short for a word of 4
+        let grid = grid(&["AB", "BA", "AB"]);
+        let search = WordSearch::new(&["ABAB", "ABA"]);
+        let found = search.find(&grid, &options(Directions::Orthogonal, true));
+
+        assert!(found.iter().all(|found| found.word == 1 && found.direction.1 == 0), "{:?}", found);
+        assert_eq!(found.len(), 2);
+    }
+}
@@ -1,15 +1,32 @@
-use std::collections::{HashMap, HashSet};
+use std::io;
+
+use super::rule_graph::{self, PrintQueue};
+use crate::utils::input::input_path;
+use crate::utils::read_as_string::{read_as_string_with, LineEnding, LineJoin, ReadOptions};
+use crate::utils::report::{format_table, ReportFormat};
+
+// the blank line between the rules and the updates has to survive the read, whichever line endings the file has
+fn read_print_queue() -> io::Result<PrintQueue> {
+    let path = input_path("src/day_5/input.txt");
+    let options = ReadOptions {
+        join: LineJoin::PreserveNewlines,
+        ..Default::default()
+    };
+    let input = read_as_string_with(path, &options)?;
+
+    if matches!(input.line_ending, LineEnding::Crlf | LineEnding::Mixed) {
+        eprintln!("Note: {} has {:?} line endings, read as LF", path, input.line_ending);
+    }
 
-use crate::utils::read_lines_as_int_arrays::read_lines_as_int_arrays;
+    rule_graph::parse(&input.text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path, e)))
+}
 
 pub fn run_a() -> std::io::Result<()> {
-    let page_ordering_rules = read_lines_as_int_arrays("src/day_5/page_ordering_rules.txt", "|")?;
-    let pages_to_produce = read_lines_as_int_arrays("src/day_5/pages_to_produce.txt", ",")?;
-    let (correctly_ordered_updates, _) = get_correct_and_incorrect_orderings(page_ordering_rules.clone(), pages_to_produce);
+    let queue = read_print_queue()?;
 
     // get sum of middle page numbers for correctly ordered updates
     let mut sum = 0;
-    for pages in correctly_ordered_updates {
+    for pages in queue.updates.iter().filter(|pages| queue.rules.is_ordered(pages)) {
         let middle_page_index = pages.len() / 2;
         sum += pages[middle_page_index];
     }
@@ -20,14 +37,16 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let page_ordering_rules = read_lines_as_int_arrays("src/day_5/page_ordering_rules.txt", "|")?;
-    let pages_to_produce = read_lines_as_int_arrays("src/day_5/pages_to_produce.txt", ",")?;
+    let queue = read_print_queue()?;
 
-    let (_, incorrectly_ordered_updates) = get_correct_and_incorrect_orderings(page_ordering_rules.clone(), pages_to_produce);
-    
     let mut sum = 0;
-    for pages in incorrectly_ordered_updates {
-        let fixed_order = topological_sort(&pages, &page_ordering_rules);
+    for pages in queue.updates.iter().filter(|pages| !queue.rules.is_ordered(pages)) {
+        let fixed_order = queue.rules.sort(pages).map_err(|cycle| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("update {:?} can't be ordered, its rules loop: {}", pages, format_cycle(&cycle)),
+            )
+        })?;
         let middle_index = fixed_order.len() / 2;
         sum += fixed_order[middle_index];
     }
@@ -37,111 +56,81 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
-fn get_correct_and_incorrect_orderings(page_ordering_rules: Vec<Vec<i32>>, pages_to_produce: Vec<Vec<i32>>) -> (Vec<Vec<i32>>, Vec<Vec<i32>>) {
-    let mut correctly_ordered_updates: Vec<Vec<i32>> = Vec::new();
-    let mut incorrectly_ordered_updates: Vec<Vec<i32>> = Vec::new();
-
-    for pages in pages_to_produce {
-        let mut correctly_ordered_update = true;
-
-        for i in 0..pages.len() {
-            let current_page = pages[i];
-            let pages_before_current = &pages[0..i];
-            let pages_after_current = &pages[i+1..];
-
-            // pages before current must be present on the left hand side of the page ordering rules
-            let left_hand_sides = find_left_hand_sides_of_page_ordering_rules_given_page_number(current_page, page_ordering_rules.clone());
-            for page in pages_before_current {
-                if !left_hand_sides.contains(page) {
-                    correctly_ordered_update = false;
-                    break;
-                }
-            }
+// the rules going round a loop, e.g. 47|53 -> 53|29 -> 29|47
+fn format_cycle(cycle: &[i32]) -> String {
+    let rules: Vec<String> = cycle.windows(2).map(|pair| format!("{}|{}", pair[0], pair[1])).collect();
+    rules.join(" -> ")
+}
 
-            // pages after current must be present on the right hand side of the page ordering rules
-            let right_hand_sides = find_right_hand_sides_of_page_ordering_rules_given_page_number(current_page, page_ordering_rules.clone());
-            for page in pages_after_current {
-                if !right_hand_sides.contains(page) {
-                    correctly_ordered_update = false;
-                    break;
-                }
-            }
-        }
+// whether each update is in order, the order it should be in, or the rules that make that impossible, then the
+// rules that never apply to any update
+pub fn report(format: ReportFormat) -> std::io::Result<String> {
+    let queue = read_print_queue()?;
 
-        if correctly_ordered_update {
-            correctly_ordered_updates.push(pages);
+    let mut updates = Vec::new();
+    for (i, pages) in queue.updates.iter().enumerate() {
+        let (status, detail) = if queue.rules.is_ordered(pages) {
+            ("ordered", None)
         } else {
-            incorrectly_ordered_updates.push(pages);
-        }
+            match queue.rules.sort(pages) {
+                Ok(fixed) => ("reordered", Some(Ok(fixed))),
+                Err(cycle) => ("cycle", Some(Err(cycle))),
+            }
+        };
+        updates.push((i + 1, pages, status, detail));
     }
-
-    return (correctly_ordered_updates, incorrectly_ordered_updates);
-}
-
-fn find_left_hand_sides_of_page_ordering_rules_given_page_number(page_number: i32, page_ordering_rules: Vec<Vec<i32>>) -> Vec<i32> {
-    let mut left_hand_sides: Vec<i32> = Vec::new();
-    for rule in page_ordering_rules {
-        if rule[1] == page_number {
-            left_hand_sides.push(rule[0]);
+    let unused = queue.rules.unused_rules(&queue.updates);
+
+    let join = |pages: &[i32], separator: &str| pages.iter().map(|page| page.to_string()).collect::<Vec<_>>().join(separator);
+
+    Ok(match format {
+        ReportFormat::Json => {
+            let objects: Vec<String> = updates
+                .iter()
+                .map(|(update, pages, status, detail)| {
+                    let (fixed, cycle) = match detail {
+                        Some(Ok(fixed)) => (format!("[{}]", join(fixed, ", ")), "null".to_string()),
+                        Some(Err(cycle)) => ("null".to_string(), format!("[{}]", join(cycle, ", "))),
+                        None => ("null".to_string(), "null".to_string()),
+                    };
+                    format!(
+                        "    {{\"update\": {}, \"pages\": [{}], \"status\": \"{}\", \"fixed\": {}, \"cycle\": {}}}",
+                        update,
+                        join(pages, ", "),
+                        status,
+                        fixed,
+                        cycle
+                    )
+                })
+                .collect();
+            let unused: Vec<String> = unused.iter().map(|(before, after)| format!("[{}, {}]", before, after)).collect();
+            format!(
+                "{{\n  \"updates\": [\n{}\n  ],\n  \"unused_rules\": [{}]\n}}\n",
+                objects.join(",\n"),
+                unused.join(", ")
+            )
         }
-    }
-    return left_hand_sides;
-}
+        ReportFormat::Table => {
+            let mut rows = vec![["Update", "Pages", "Status", "Fixed order or loop"]
+                .iter()
+                .map(|header| header.to_string())
+                .collect::<Vec<String>>()];
+            for (update, pages, status, detail) in &updates {
+                let detail = match detail {
+                    Some(Ok(fixed)) => join(fixed, ","),
+                    Some(Err(cycle)) => format_cycle(cycle),
+                    None => "-".to_string(),
+                };
+                rows.push(vec![update.to_string(), join(pages, ","), status.to_string(), detail]);
+            }
 
-fn find_right_hand_sides_of_page_ordering_rules_given_page_number(page_number: i32, page_ordering_rules: Vec<Vec<i32>>) -> Vec<i32> {
-    let mut right_hand_sides: Vec<i32> = Vec::new();
-    for rule in page_ordering_rules {
-        if rule[0] == page_number {
-            right_hand_sides.push(rule[1]);
+            let unused: Vec<String> = unused.iter().map(|(before, after)| format!("{}|{}", before, after)).collect();
+            format!(
+                "{}\n{} rules never apply: {}\n",
+                format_table(&rows),
+                unused.len(),
+                if unused.is_empty() { "-".to_string() } else { unused.join(", ") }
+            )
         }
-    }
-    return right_hand_sides;
+    })
 }
-
-fn topological_sort(pages: &Vec<i32>, rules: &Vec<Vec<i32>>) -> Vec<i32> {
-    // Build adjacency list and in-degree count for pages
-    let mut graph: HashMap<i32, Vec<i32>> = HashMap::new();
-    let mut in_degree: HashMap<i32, i32> = HashMap::new();
-    let page_set: HashSet<i32> = pages.iter().cloned().collect();
-
-    // Initialize in-degree for all pages
-    for &page in pages {
-        graph.entry(page).or_insert(Vec::new());
-        in_degree.entry(page).or_insert(0);
-    }
-
-    // Build the graph using only rules that involve pages in our set
-    for rule in rules {
-        let from = rule[0];
-        let to = rule[1];
-        if page_set.contains(&from) && page_set.contains(&to) {
-            graph.entry(from).or_default().push(to);
-            *in_degree.entry(to).or_default() += 1;
-        }
-    }
-
-    // Find all nodes with in-degree 0
-    let mut result = Vec::new();
-    let mut queue: Vec<i32> = pages.iter()
-        .filter(|&&page| in_degree.get(&page).unwrap_or(&0) == &0)
-        .cloned()
-        .collect();
-
-    while !queue.is_empty() {
-        // Sort queue to ensure deterministic ordering when multiple nodes have in-degree 0
-        queue.sort_unstable();
-        let current = queue.remove(0);
-        result.push(current);
-
-        if let Some(neighbors) = graph.get(&current) {
-            for &next in neighbors {
-                *in_degree.get_mut(&next).unwrap() -= 1;
-                if in_degree[&next] == 0 {
-                    queue.push(next);
-                }
-            }
-        }
-    }
-
-    result
-}
\ No newline at end of file
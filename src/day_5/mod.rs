@@ -1 +1,2 @@
 pub mod day_5;
+mod rule_graph;
\ No newline at end of file
@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+
+// The page ordering rules as a graph from each page to the pages that must come after it, with every rule
+// indexed by its pair of pages so checking one is a single lookup
+
+pub struct RuleGraph {
+    // in the order they were given
+    rules: Vec<(i32, i32)>,
+    index: HashMap<(i32, i32), usize>,
+    after: HashMap<i32, Vec<i32>>,
+}
+
+// the rules and the updates, as read from the puzzle's input
+pub struct PrintQueue {
+    pub rules: RuleGraph,
+    pub updates: Vec<Vec<i32>>,
+}
+
+impl RuleGraph {
+    pub fn new(rules: Vec<(i32, i32)>) -> Self {
+        let mut index = HashMap::with_capacity(rules.len());
+        let mut after: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (i, &(before, later)) in rules.iter().enumerate() {
+            if index.insert((before, later), i).is_none() {
+                after.entry(before).or_default().push(later);
+            }
+        }
+
+        RuleGraph { rules, index, after }
+    }
+
+    pub fn has_rule(&self, before: i32, after: i32) -> bool {
+        self.index.contains_key(&(before, after))
+    }
+
+    // every page must have a rule putting it before each page that follows it
+    pub fn is_ordered(&self, update: &[i32]) -> bool {
+        (0..update.len()).all(|i| update[i + 1..].iter().all(|&later| self.has_rule(update[i], later)))
+    }
+
+    // the rules between the pages of the update, as a graph
+    fn subgraph(&self, update: &[i32]) -> HashMap<i32, Vec<i32>> {
+        let pages: HashSet<i32> = update.iter().copied().collect();
+        update
+            .iter()
+            .map(|&page| {
+                let later = self.after.get(&page).map_or(Vec::new(), |later| {
+                    later.iter().copied().filter(|later| pages.contains(later)).collect()
+                });
+                (page, later)
+            })
+            .collect()
+    }
+
+    // the update put in an order that follows every rule between its pages, taking the smallest page first
+    // whenever there's a choice. a loop in the rules makes that impossible, so that's returned instead
+    pub fn sort(&self, update: &[i32]) -> Result<Vec<i32>, Vec<i32>> {
+        let graph = self.subgraph(update);
+        let mut in_degree: HashMap<i32, usize> = update.iter().map(|&page| (page, 0)).collect();
+        for later in graph.values().flatten() {
+            *in_degree.entry(*later).or_default() += 1;
+        }
+
+        let mut queue: Vec<i32> = update.iter().copied().filter(|page| in_degree[page] == 0).collect();
+        let mut result = Vec::with_capacity(update.len());
+        while !queue.is_empty() {
+            queue.sort_unstable();
+            let current = queue.remove(0);
+            result.push(current);
+
+            for later in &graph[&current] {
+                let degree = in_degree.get_mut(later).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push(*later);
+                }
+            }
+        }
+
+        if result.len() < in_degree.len() {
+            return Err(self.find_cycle(update).unwrap_or_default());
+        }
+        Ok(result)
+    }
+
+    // a loop in the rules between the update's pages, as the pages going round it with the first repeated at
+    // the end, e.g. [47, 53, 29, 47] for 47|53, 53|29 and 29|47
+    pub fn find_cycle(&self, update: &[i32]) -> Option<Vec<i32>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Unvisited,
+            OnPath,
+            Done,
+        }
+
+        fn visit(
+            page: i32,
+            graph: &HashMap<i32, Vec<i32>>,
+            states: &mut HashMap<i32, State>,
+            path: &mut Vec<i32>,
+        ) -> Option<Vec<i32>> {
+            states.insert(page, State::OnPath);
+            path.push(page);
+
+            for &later in &graph[&page] {
+                match states[&later] {
+                    State::OnPath => {
+                        let start = path.iter().position(|&p| p == later).unwrap();
+                        let mut cycle = path[start..].to_vec();
+                        cycle.push(later);
+                        return Some(cycle);
+                    }
+                    State::Unvisited => {
+                        if let Some(cycle) = visit(later, graph, states, path) {
+                            return Some(cycle);
+                        }
+                    }
+                    State::Done => {}
+                }
+            }
+
+            path.pop();
+            states.insert(page, State::Done);
+            None
+        }
+
+        let graph = self.subgraph(update);
+        let mut states: HashMap<i32, State> = update.iter().map(|&page| (page, State::Unvisited)).collect();
+        for &page in update {
+            if states[&page] == State::Unvisited {
+                if let Some(cycle) = visit(page, &graph, &mut states, &mut Vec::new()) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    // the rules whose two pages are never in the same update, so they never decide anything
+    pub fn unused_rules(&self, updates: &[Vec<i32>]) -> Vec<(i32, i32)> {
+        let mut used = vec![false; self.rules.len()];
+        for update in updates {
+            for (i, &before) in update.iter().enumerate() {
+                for &other in &update[i + 1..] {
+                    for pair in [(before, other), (other, before)] {
+                        if let Some(&rule) = self.index.get(&pair) {
+                            used[rule] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        // a rule given more than once is only reported at its first
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|&(i, rule)| self.index[rule] == i && !used[i])
+            .map(|(_, &rule)| rule)
+            .collect()
+    }
+}
+
+// the rules, a blank line, then the updates, as the puzzle gives them
+pub fn parse(input: &str) -> Result<PrintQueue, String> {
+    let mut lines = input.lines().enumerate();
+
+    let mut rules = Vec::new();
+    for (i, line) in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            if rules.is_empty() {
+                continue;
+            }
+            break;
+        }
+        let (before, after) = line
+            .split_once('|')
+            .ok_or_else(|| format!("line {}: expected a rule like 47|53, got {}", i + 1, line))?;
+        rules.push((parse_page(before, i)?, parse_page(after, i)?));
+    }
+
+    let mut updates = Vec::new();
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let update = line.split(',').map(|page| parse_page(page, i)).collect::<Result<Vec<i32>, String>>()?;
+        updates.push(update);
+    }
+
+    Ok(PrintQueue {
+        rules: RuleGraph::new(rules),
+        updates,
+    })
+}
+
+fn parse_page(page: &str, line: usize) -> Result<i32, String> {
+    page.trim()
+        .parse()
+        .map_err(|_| format!("line {}: {:?} is not a page number", line + 1, page))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> PrintQueue {
+        parse(include_str!("input_example.txt")).unwrap()
+    }
+
+    #[test]
+    fn parses_the_example() {
+        let queue = example();
+        assert_eq!(queue.rules.rules.len(), 21);
+        assert_eq!(queue.rules.rules[0], (47, 53));
+        assert_eq!(queue.updates.len(), 6);
+        assert_eq!(queue.updates[0], vec![75, 47, 61, 53, 29]);
+    }
+
+    #[test]
+    fn is_ordered() {
+        let queue = example();
+        let ordered: Vec<bool> = queue.updates.iter().map(|update| queue.rules.is_ordered(update)).collect();
+        assert_eq!(ordered, vec![true, true, true, false, false, false]);
+    }
+
+    #[test]
+    fn sort() {
+        let queue = example();
+        assert_eq!(queue.rules.sort(&[75, 97, 47, 61, 53]), Ok(vec![97, 75, 47, 61, 53]));
+        assert_eq!(queue.rules.sort(&[61, 13, 29]), Ok(vec![61, 29, 13]));
+        assert_eq!(queue.rules.sort(&[97, 13, 75, 29, 47]), Ok(vec![97, 75, 47, 29, 13]));
+        // an update that's already in order stays as it is
+        assert_eq!(queue.rules.sort(&queue.updates[0]), Ok(queue.updates[0].clone()));
+    }
+
+    #[test]
+    fn find_cycle() {
+        let rules = RuleGraph::new(vec![(47, 53), (53, 29), (29, 47), (29, 13)]);
+        assert_eq!(rules.find_cycle(&[47, 53, 29]), Some(vec![47, 53, 29, 47]));
+        assert_eq!(rules.sort(&[47, 53, 29, 13]), Err(vec![47, 53, 29, 47]));
+        // without 47 the loop is broken
+        assert_eq!(rules.find_cycle(&[53, 29, 13]), None);
+
+        let queue = example();
+        assert!(queue.updates.iter().all(|update| queue.rules.find_cycle(update).is_none()));
+    }
+
+    #[test]
+    fn unused_rules() {
+        let queue = example();
+        assert!(queue.rules.unused_rules(&queue.updates).is_empty());
+        assert_eq!(queue.rules.unused_rules(&[]).len(), 21);
+
+        // a rule given twice is only reported once, and not at all when it's used
+        let rules = RuleGraph::new(vec![(1, 2), (3, 4), (1, 2), (3, 4), (5, 6)]);
+        assert_eq!(rules.unused_rules(&[vec![4, 3]]), vec![(1, 2), (5, 6)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |input: &str| parse(input).err().unwrap();
+        assert_eq!(error("47|53\n47-53\n"), "line 2: expected a rule like 47|53, got 47-53");
+        assert_eq!(error("47|x\n"), "line 1: \"x\" is not a page number");
+        assert_eq!(error("47|53\n\n47,53\n53,,47\n"), "line 4: \"\" is not a page number");
+    }
+
+    #[test]
+    fn parse_skips_blank_lines_and_carriage_returns() {
+        let queue = parse("\r\n47|53\r\n53|29\r\n\r\n47,53,29\r\n\r\n").unwrap();
+        assert_eq!(queue.rules.rules, vec![(47, 53), (53, 29)]);
+        assert_eq!(queue.updates, vec![vec![47, 53, 29]]);
+    }
+}
@@ -1,5 +1,14 @@
 use std::collections::HashSet;
 
+use image::RgbImage;
+
+use crate::render::animation::GifWriter;
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette::{self, Palette};
+use crate::render::terminal::TerminalAnimation;
+use crate::utils::cancellation::{self, CancellationToken};
+use crate::utils::input::input_path;
 use crate::utils::read_as_matrix::read_as_matrix;
 
 #[derive(PartialEq, Clone, Debug, Hash, Eq)]  // Added Hash, Eq for HashSet
@@ -23,19 +32,15 @@ struct GuardState {
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let input = read_as_matrix("src/day_6/input.txt");
-    let (guard_position, guard_direction) = find_guard_initial_position_and_direction(&input);
-    let mut guard_history = Vec::new();
-    guard_history.push(guard_position.clone());
-    
-    simulate_guard_path(&input, guard_position, guard_direction, &mut guard_history);
+    let input = read_as_matrix(input_path("src/day_6/input.txt"));
+    let guard_trail = get_guard_trail(&input)?;
 
-    println!("Total unique points visited by the guard: {}", guard_history.len());
+    println!("Total unique points visited by the guard: {}", guard_trail.len());
     Ok(())
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let mut input = read_as_matrix("src/day_6/input.txt");
+    let mut input = read_as_matrix(input_path("src/day_6/input.txt"));
     let (guard_position, guard_direction) = find_guard_initial_position_and_direction(&input);
     
     // First, get the guard's original path
@@ -77,8 +82,11 @@ pub fn run_b() -> std::io::Result<()> {
     }
 
     let mut possible_positions = Vec::new();
+    let token = cancellation::current();
     // Now only test positions adjacent to the original path
     for pos in adjacent_positions {
+        token.check()?;
+
         // Place obstacle
         input[pos.x as usize][pos.y as usize] = '#';
 
@@ -104,6 +112,130 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
+// draws the lab with every tile the guard walks over before leaving
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let input = read_as_matrix(input_path("src/day_6/input.txt"));
+    let trail = get_guard_trail(&input)?;
+    Ok(renderer.render_chars(&input, &guard_palette(), &trail_overlays(&trail)))
+}
+
+// same as render, but as an animation of the guard walking, one frame every `steps_per_frame` tiles
+pub fn render_animation(renderer: &GridRenderer, gif: &mut GifWriter, steps_per_frame: usize) -> std::io::Result<()> {
+    let input = read_as_matrix(input_path("src/day_6/input.txt"));
+    let trail = get_guard_trail(&input)?;
+    let lab = renderer.render_chars(&input, &guard_palette(), &[]);
+
+    let steps_per_frame = steps_per_frame.max(1);
+    let mut steps = steps_per_frame.min(trail.len());
+    loop {
+        let mut frame = lab.clone();
+        for overlay in trail_overlays(&trail[..steps]) {
+            renderer.draw_overlay(&mut frame, &overlay);
+        }
+        gif.add_frame(&frame)?;
+
+        if steps == trail.len() {
+            break;
+        }
+        steps = (steps + steps_per_frame).min(trail.len());
+    }
+
+    Ok(())
+}
+
+// walks the guard through the lab in the terminal, one step per frame
+pub fn animate(animation: &mut TerminalAnimation) -> std::io::Result<()> {
+    let mut input = read_as_matrix(input_path("src/day_6/input.txt"));
+    let (mut position, mut direction) = find_guard_initial_position_and_direction(&input);
+    // the snapshot draws the guard wherever it is now, so its starting tile is just floor
+    input[position.x as usize][position.y as usize] = '.';
+
+    let mut visited = HashSet::from([position.clone()]);
+    let mut states = HashSet::new();
+
+    loop {
+        let snapshot = generate_snapshot(&input, &visited, &position, &direction);
+        let next_pos = get_next_position(&position, &direction);
+        let looping = !states.insert(GuardState { position: position.clone(), direction: direction.clone() });
+
+        let status = if looping {
+            format!("the guard is stuck in a loop after visiting {} tiles", visited.len())
+        } else if !is_within_bounds(&next_pos, &input) {
+            format!("the guard left the lab after visiting {} tiles", visited.len())
+        } else {
+            format!("visited {} tiles", visited.len())
+        };
+
+        if !animation.show(&snapshot, &status) || looping || !is_within_bounds(&next_pos, &input) {
+            return Ok(());
+        }
+
+        if input[next_pos.x as usize][next_pos.y as usize] == '#' {
+            direction = turn_right(&direction);
+        } else {
+            position = next_pos;
+            visited.insert(position.clone());
+        }
+    }
+}
+
+// the lab as text, with visited tiles marked 'X' and the guard drawn facing the way it's walking
+fn generate_snapshot(matrix: &[Vec<char>], visited: &HashSet<Point>, guard: &Point, direction: &Direction) -> String {
+    let mut output = String::new();
+
+    for (x, row) in matrix.iter().enumerate() {
+        for (y, &c) in row.iter().enumerate() {
+            let pos = Point { x: x as i32, y: y as i32 };
+            let char = if pos == *guard {
+                match direction {
+                    Direction::Up => '^',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                    Direction::Right => '>',
+                }
+            } else if visited.contains(&pos) {
+                'X'
+            } else {
+                c
+            };
+            output.push(char);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn guard_palette() -> Palette {
+    Palette::maze()
+        .with('^', palette::BACKGROUND)
+        .with('>', palette::BACKGROUND)
+        .with('v', palette::BACKGROUND)
+        .with('<', palette::BACKGROUND)
+}
+
+// the guard's visited tiles as (x, y) cells, in the order they were first visited
+fn get_guard_trail(input: &Vec<Vec<char>>) -> std::io::Result<Vec<(usize, usize)>> {
+    let (guard_position, guard_direction) = find_guard_initial_position_and_direction(input);
+    let mut guard_history = vec![guard_position.clone()];
+
+    let token = cancellation::current();
+    simulate_guard_path(input, guard_position, guard_direction, &mut guard_history, &token);
+    // on some grids the guard never leaves, in which case we only stop once cancelled
+    token.check()?;
+
+    // points here are (row, column), the renderer wants (column, row)
+    Ok(guard_history.iter().map(|p| (p.y as usize, p.x as usize)).collect())
+}
+
+fn trail_overlays(trail: &[(usize, usize)]) -> Vec<Overlay> {
+    let mut overlays = vec![Overlay::Trail { cells: trail.to_vec(), color: palette::BLUE }];
+    if let Some(&guard) = trail.last() {
+        overlays.push(Overlay::Markers { cells: vec![guard], color: palette::GOLD });
+    }
+    overlays
+}
+
 fn simulate_guard_path_with_loop_detection(
     matrix: &Vec<Vec<char>>,
     start_pos: Point,
@@ -144,11 +276,15 @@ fn simulate_guard_path_with_loop_detection(
     }
 }
 
-fn simulate_guard_path(matrix: &Vec<Vec<char>>, start_pos: Point, start_direction: Direction, history: &mut Vec<Point>) {
+fn simulate_guard_path(matrix: &Vec<Vec<char>>, start_pos: Point, start_direction: Direction, history: &mut Vec<Point>, token: &CancellationToken) {
     let mut current_pos = start_pos;
     let mut current_direction = start_direction;
 
     loop {
+        if token.is_cancelled() {
+            break;
+        }
+
         // Check if there's an obstruction in front
         let next_pos = get_next_position(&current_pos, &current_direction);
         
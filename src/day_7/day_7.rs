@@ -1,4 +1,6 @@
-use crate::utils::read_lines::read_lines;
+use crate::utils::cancellation::{self, CancellationToken};
+use crate::utils::input::input_path;
+use crate::utils::stream_lines::{open_buffered, stream_lines};
 
 // create a type with 'solution', i64 and 'numbers', vec i64
 struct Solution {
@@ -15,27 +17,29 @@ pub fn run_b() -> std::io::Result<()> {
 }
 
 fn run_for_given_operators(operators: &[&str]) -> std::io::Result<()> {
-    let lines = read_lines("src/day_7/input.txt")?;
-    let mut solutions: Vec<Solution> = Vec::new();
+    let reader = open_buffered(input_path("src/day_7/input.txt"))?;
+    let mut sum = 0;
+    let token = cancellation::current();
 
-    for line in lines {
-        let input: Vec<&str> = line.split(":").collect();
-        let solution = input[0].parse::<i64>().unwrap();
-        let numbers: Vec<i64> = input[1].trim().split(" ")
-            .map(|x| x.parse::<i64>().unwrap())
-            .collect();
+    // each equation is solved as soon as it's read, so only one is held in memory at a time
+    for line in stream_lines(reader) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
 
-        solutions.push(Solution {
-            solution: solution,
-            numbers: numbers,
-        });
-    }
+        let input: Vec<&str> = line.split(":").collect();
+        let solution = Solution {
+            solution: input[0].parse::<i64>().unwrap(),
+            numbers: input[1].trim().split(" ")
+                .map(|x| x.parse::<i64>().unwrap())
+                .collect(),
+        };
 
-    let mut true_solutions: Vec<i64> = Vec::new();
-    
-    for solution in solutions {
         let num_operators = solution.numbers.len() - 1;
-        let operator_combinations = generate_all_operator_combinations(&operators, num_operators);
+        let operator_combinations = generate_all_operator_combinations(&operators, num_operators, &token);
+        // the combinations are cut short when cancelled, so don't trust them
+        token.check()?;
         let mut found = false;
 
         // println!("\nTrying to find: {} with numbers {:?}", solution.solution, solution.numbers);
@@ -45,7 +49,7 @@ fn run_for_given_operators(operators: &[&str]) -> std::io::Result<()> {
             
             if result == solution.solution {
                 // println!("Found: {} = {}", expression, result);
-                true_solutions.push(solution.solution);  // Note: pushing solution.solution, not result
+                sum += solution.solution;  // Note: adding solution.solution, not result
                 found = true;
                 break;
             }
@@ -56,20 +60,25 @@ fn run_for_given_operators(operators: &[&str]) -> std::io::Result<()> {
         }
     }
 
-    let sum = true_solutions.iter().sum::<i64>();
     println!("\nSum of true solutions: {}", sum);
     Ok(())
 }
 
-fn generate_all_operator_combinations<'a>(operators: &[&'a str], length: usize) -> Vec<Vec<&'a str>> {
+fn generate_all_operator_combinations<'a>(operators: &[&'a str], length: usize, token: &CancellationToken) -> Vec<Vec<&'a str>> {
     let mut result = Vec::new();
     
     fn generate_recursive<'a>(
         current: &mut Vec<&'a str>,
         operators: &[&'a str],
         length: usize,
-        result: &mut Vec<Vec<&'a str>>
+        result: &mut Vec<Vec<&'a str>>,
+        token: &CancellationToken
     ) {
+        // this grows exponentially with the length of the equation, so stop early if we've run out of time
+        if token.is_cancelled() {
+            return;
+        }
+
         if current.len() == length {
             result.push(current.clone());
             return;
@@ -77,12 +86,12 @@ fn generate_all_operator_combinations<'a>(operators: &[&'a str], length: usize)
         
         for &op in operators {
             current.push(op);
-            generate_recursive(current, operators, length, result);
+            generate_recursive(current, operators, length, result, token);
             current.pop();
         }
     }
     
-    generate_recursive(&mut Vec::new(), operators, length, &mut result);
+    generate_recursive(&mut Vec::new(), operators, length, &mut result, token);
     result
 }
 
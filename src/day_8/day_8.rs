@@ -1,31 +1,38 @@
+use image::RgbImage;
+
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::overlay::Overlay;
+use crate::render::palette::{self, distinct_color};
+use crate::utils::input::input_path;
 use crate::utils::read_as_matrix::read_as_matrix;
+use crate::utils::sparse_grid::{read_as_sparse_grid, Bounds, SparseGrid, SparseGridOptions};
 
 #[derive(PartialEq, Clone, Debug, Hash, Eq)]  // Added Hash, Eq for HashSet
 struct Point {
-    x: i32,
-    y: i32,
+    x: i64,
+    y: i64,
+}
+
+// every antenna keyed by its position, the bounds are those of the whole map
+// anything that isn't an antenna is empty space, like the # some puzzle examples mark the antinodes with
+fn read_antennas() -> std::io::Result<(SparseGrid<char>, Bounds)> {
+    let options = SparseGridOptions {
+        skip_unknown: true,
+        ..Default::default()
+    };
+    let antennas = read_as_sparse_grid(input_path("src/day_8/input.txt"), &options, |c| {
+        c.is_ascii_alphanumeric().then_some(c)
+    })?;
+    let bounds = antennas.bounds().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "the map is empty")
+    })?;
+    Ok((antennas, bounds))
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let matrix = read_as_matrix("src/day_8/input.txt");
+    let (antennas, bounds) = read_antennas()?;
 
-    let mut unique_colocation_points = Vec::<Point>::new();
-    // for a-z, A-Z, and 0-9, find the location of each antenna and the unique colocation points
-    for antennae_char in 'a'..='z' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_collinearity_points_within_matrix(&matrix, &antenna_locations, &unique_colocation_points);
-        unique_colocation_points.extend(new_unique_colocation_points);
-    }
-    for antennae_char in 'A'..='Z' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_collinearity_points_within_matrix(&matrix, &antenna_locations, &unique_colocation_points);
-        unique_colocation_points.extend(new_unique_colocation_points);
-    }
-    for antennae_char in '0'..='9' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_collinearity_points_within_matrix(&matrix, &antenna_locations, &unique_colocation_points);
-        unique_colocation_points.extend(new_unique_colocation_points);
-    }
+    let unique_colocation_points = find_antinodes(&antennas, &bounds);
 
     // print length of unique colocation points
     println!("Number of unique colocation points: {}", unique_colocation_points.len());
@@ -34,23 +41,23 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let matrix = read_as_matrix("src/day_8/input.txt");
+    let (antennas, bounds) = read_antennas()?;
 
     let mut unique_harmonic_points = Vec::<Point>::new();
     // for a-z, A-Z, and 0-9, find the location of each antenna and the unique colocation points
     for antennae_char in 'a'..='z' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&matrix, &antenna_locations, &unique_harmonic_points);
+        let antenna_locations = get_location_of_antennae_with_letter(&antennas, antennae_char);
+        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&bounds, &antenna_locations, &unique_harmonic_points);
         unique_harmonic_points.extend(new_unique_colocation_points);
     }
     for antennae_char in 'A'..='Z' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&matrix, &antenna_locations, &unique_harmonic_points);
+        let antenna_locations = get_location_of_antennae_with_letter(&antennas, antennae_char);
+        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&bounds, &antenna_locations, &unique_harmonic_points);
         unique_harmonic_points.extend(new_unique_colocation_points);
     }
     for antennae_char in '0'..='9' {
-        let antenna_locations = get_location_of_antennae_with_letter(&matrix, antennae_char);
-        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&matrix, &antenna_locations, &unique_harmonic_points);
+        let antenna_locations = get_location_of_antennae_with_letter(&antennas, antennae_char);
+        let new_unique_colocation_points = get_unique_resonant_harmonic_points_within_matrix(&bounds, &antenna_locations, &unique_harmonic_points);
         unique_harmonic_points.extend(new_unique_colocation_points);
     }
 
@@ -60,19 +67,46 @@ pub fn run_b() -> std::io::Result<()> {
     Ok(())
 }
 
-fn get_location_of_antennae_with_letter(matrix: &Vec<Vec<char>>, letter: char) -> Vec<Point> {
-    let mut locations = Vec::new();
-    for (y, row) in matrix.iter().enumerate() {
-        for (x, cell) in row.iter().enumerate() {
-            if *cell == letter {
-                locations.push(Point { x: x as i32, y: y as i32 });
-            }
+// draws every antenna in a colour for its frequency, with a dot on each antinode
+pub fn render(renderer: &GridRenderer) -> std::io::Result<RgbImage> {
+    let matrix = read_as_matrix(input_path("src/day_8/input.txt"));
+    let (antennas, bounds) = read_antennas()?;
+    let antinodes = find_antinodes(&antennas, &bounds);
+
+    let overlays = [Overlay::Markers {
+        cells: antinodes.iter().map(|p| (p.x as usize, p.y as usize)).collect(),
+        color: palette::GOLD,
+    }];
+
+    Ok(renderer.render(&matrix, |&c| {
+        if c.is_ascii_alphanumeric() {
+            distinct_color(c as usize)
+        } else {
+            palette::BACKGROUND
         }
+    }, &overlays))
+}
+
+fn find_antinodes(antennas: &SparseGrid<char>, bounds: &Bounds) -> Vec<Point> {
+    let mut unique_colocation_points = Vec::<Point>::new();
+    // for a-z, A-Z, and 0-9, find the location of each antenna and the unique colocation points
+    for antennae_char in ('a'..='z').chain('A'..='Z').chain('0'..='9') {
+        let antenna_locations = get_location_of_antennae_with_letter(antennas, antennae_char);
+        let new_unique_colocation_points = get_unique_collinearity_points_within_matrix(bounds, &antenna_locations, &unique_colocation_points);
+        unique_colocation_points.extend(new_unique_colocation_points);
     }
-    locations
+    unique_colocation_points
+}
+
+fn get_location_of_antennae_with_letter(antennas: &SparseGrid<char>, letter: char) -> Vec<Point> {
+    antennas
+        .iter()
+        .filter(|(_, &cell)| cell == letter)
+        .map(|((x, y), _)| Point { x, y })
+        .collect()
 }
 
-fn get_unique_collinearity_points_within_matrix(matrix: &Vec<Vec<char>>, points: &Vec<Point>, existing_points: &Vec<Point>) -> Vec<Point> {
+fn get_unique_collinearity_points_within_matrix(bounds: &Bounds, points: &[Point], existing_points: &[Point]) -> Vec<Point> {
     // a collinear point is a point that is on the same line as two other points, but d1 from one and d2 from the other
     // if the calculated collinear point is outside the matrix bounds, it is not a valid collinear point
     // we also need to check if the collinear point is already in the existing points list, then we continue to the next point
@@ -85,7 +119,7 @@ fn get_unique_collinearity_points_within_matrix(matrix: &Vec<Vec<char>>, points:
             let d1 = point1.x - point2.x;
             let d2 = point1.y - point2.y;
             let collinear_point = Point { x: point1.x + d1, y: point1.y + d2 };
-            if collinear_point.x < 0 || collinear_point.x >= matrix[0].len() as i32 || collinear_point.y < 0 || collinear_point.y >= matrix.len() as i32 {
+            if !bounds.contains(collinear_point.x, collinear_point.y) {
                 continue;
             }
             if existing_points.contains(&collinear_point) {
@@ -98,7 +132,7 @@ fn get_unique_collinearity_points_within_matrix(matrix: &Vec<Vec<char>>, points:
 }
 
 fn get_unique_resonant_harmonic_points_within_matrix(
-    matrix: &Vec<Vec<char>>, 
+    bounds: &Bounds, 
     points: &Vec<Point>, 
     existing_points: &Vec<Point>
 ) -> Vec<Point> {
@@ -120,7 +154,7 @@ fn get_unique_resonant_harmonic_points_within_matrix(
             }
 
             // Reduce (dx, dy) to its simplest form to ensure we generate every harmonic point
-            let gcd = gcd(dx.abs() as i32, dy.abs() as i32);
+            let gcd = gcd(dx.abs(), dy.abs());
             let step_x = dx / gcd;
             let step_y = dy / gcd;
 
@@ -129,7 +163,7 @@ fn get_unique_resonant_harmonic_points_within_matrix(
             loop {
                 let new_x = point1.x + step_x * k;
                 let new_y = point1.y + step_y * k;
-                if new_x < 0 || new_x >= matrix[0].len() as i32 || new_y < 0 || new_y >= matrix.len() as i32 {
+                if !bounds.contains(new_x, new_y) {
                     break; // out of bounds
                 }
                 let new_point = Point { x: new_x, y: new_y };
@@ -149,7 +183,7 @@ fn get_unique_resonant_harmonic_points_within_matrix(
             loop {
                 let new_x = point1.x - step_x * k;
                 let new_y = point1.y - step_y * k;
-                if new_x < 0 || new_x >= matrix[0].len() as i32 || new_y < 0 || new_y >= matrix.len() as i32 {
+                if !bounds.contains(new_x, new_y) {
                     break; // out of bounds
                 }
                 let new_point = Point { x: new_x, y: new_y };
@@ -168,6 +202,6 @@ fn get_unique_resonant_harmonic_points_within_matrix(
 }
 
 // Helper function to compute gcd
-fn gcd(a: i32, b: i32) -> i32 {
+fn gcd(a: i64, b: i64) -> i64 {
     if b == 0 { a } else { gcd(b, a % b) }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::utils::input::input_path;
 use crate::utils::read_as_string::read_as_string;
 
 #[derive(Clone)]
@@ -7,7 +8,7 @@ struct FileBlock {
 }
 
 pub fn run_a() -> std::io::Result<()> {
-    let disk_state = read_as_string("src/day_9/input.txt")?;
+    let disk_state = read_as_string(input_path("src/day_9/input.txt"))?;
     
     let file_blocks = build_file_blocks(&disk_state);
 
@@ -21,7 +22,7 @@ pub fn run_a() -> std::io::Result<()> {
 }
 
 pub fn run_b() -> std::io::Result<()> {
-    let disk_state = read_as_string("src/day_9/input.txt")?;
+    let disk_state = read_as_string(input_path("src/day_9/input.txt"))?;
     
     let file_blocks = build_file_blocks(&disk_state);
 
@@ -1,3 +1,6 @@
+use std::env;
+use std::io::{self, Write};
+use std::process;
 use std::time::Instant;
 
 mod day_1;
@@ -25,490 +28,128 @@ mod day_6;
 mod day_7;
 mod day_8;
 mod day_9;
+mod render;
+mod runner;
 mod utils;
 
-fn main() {
-    // for each day, call run_a and run_b, except for day 25, which only has run_a
-    let start_total = Instant::now();
-
-    let start = Instant::now();
-    if let Err(e) = day_1::day_1::run_a() {
-        eprintln!("Error running day 1 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_1::day_1::run_b() {
-        eprintln!("Error running day 1 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    // now do the same for all other days
-    let start = Instant::now();
-    if let Err(e) = day_2::day_2::run_a() {
-        eprintln!("Error running day 2 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_2::day_2::run_b() {
-        eprintln!("Error running day 2 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_3::day_3::run_a() {
-        eprintln!("Error running day 3 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_3::day_3::run_b() {
-        eprintln!("Error running day 3 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_4::day_4::run_a() {
-        eprintln!("Error running day 4 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_4::day_4::run_b() {
-        eprintln!("Error running day 4 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_5::day_5::run_a() {
-        eprintln!("Error running day 5 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_5::day_5::run_b() {
-        eprintln!("Error running day 5 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_6::day_6::run_a() {
-        eprintln!("Error running day 6 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_6::day_6::run_b() {
-        eprintln!("Error running day 6 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_7::day_7::run_a() {
-        eprintln!("Error running day 7 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_7::day_7::run_b() {
-        eprintln!("Error running day 7 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_8::day_8::run_a() {
-        eprintln!("Error running day 8 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_8::day_8::run_b() {
-        eprintln!("Error running day 8 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_9::day_9::run_a() {
-        eprintln!("Error running day 9 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_9::day_9::run_b() {
-        eprintln!("Error running day 9 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_1::day_1::run_a() {
-        eprintln!("Error running day 1 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_1::day_1::run_b() {
-        eprintln!("Error running day 1 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_2::day_2::run_a() {
-        eprintln!("Error running day 2 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_2::day_2::run_b() {
-        eprintln!("Error running day 2 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_3::day_3::run_a() {
-        eprintln!("Error running day 3 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_3::day_3::run_b() {
-        eprintln!("Error running day 3 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_4::day_4::run_a() {
-        eprintln!("Error running day 4 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_4::day_4::run_b() {
-        eprintln!("Error running day 4 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_5::day_5::run_a() {
-        eprintln!("Error running day 5 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_5::day_5::run_b() {
-        eprintln!("Error running day 5 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_6::day_6::run_a() {
-        eprintln!("Error running day 6 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_6::day_6::run_b() {
-        eprintln!("Error running day 6 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_7::day_7::run_a() {
-        eprintln!("Error running day 7 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_7::day_7::run_b() {
-        eprintln!("Error running day 7 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_8::day_8::run_a() {
-        eprintln!("Error running day 8 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
+use runner::cli::{self, Command, RunOptions};
+use runner::parts::{self, Outcome};
 
-    let start = Instant::now();
-    if let Err(e) = day_8::day_8::run_b() {
-        eprintln!("Error running day 8 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_9::day_9::run_a() {
-        eprintln!("Error running day 9 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_9::day_9::run_b() {
-        eprintln!("Error running day 9 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_10::day_10::run_a() {
-        eprintln!("Error running day 10 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_10::day_10::run_b() {
-        eprintln!("Error running day 10 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_11::day_11::run_a() {
-        eprintln!("Error running day 11 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_11::day_11::run_b() {
-        eprintln!("Error running day 11 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_12::day_12::run_a() {
-        eprintln!("Error running day 12 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_12::day_12::run_b() {
-        eprintln!("Error running day 12 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_13::day_13::run_a() {
-        eprintln!("Error running day 13 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_13::day_13::run_b() {
-        eprintln!("Error running day 13 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_14::day_14::run_a() {
-        eprintln!("Error running day 14 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_14::day_14::run_b() {
-        eprintln!("Error running day 14 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_15::day_15::run_a() {
-        eprintln!("Error running day 15 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_15::day_15::run_b() {
-        eprintln!("Error running day 15 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_16::day_16::run_a() {
-        eprintln!("Error running day 16 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_16::day_16::run_b() {
-        eprintln!("Error running day 16 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_17::day_17::run_a() {
-        eprintln!("Error running day 17 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_17::day_17::run_b() {
-        eprintln!("Error running day 17 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_18::day_18::run_a() {
-        eprintln!("Error running day 18 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_18::day_18::run_b() {
-        eprintln!("Error running day 18 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_19::day_19::run_a() {
-        eprintln!("Error running day 19 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_19::day_19::run_b() {
-        eprintln!("Error running day 19 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_20::day_20::run_a() {
-        eprintln!("Error running day 20 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_20::day_20::run_b() {
-        eprintln!("Error running day 20 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_21::day_21::run_a() {
-        eprintln!("Error running day 21 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_21::day_21::run_b() {
-        eprintln!("Error running day 21 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_22::day_22::run_a() {
-        eprintln!("Error running day 22 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_22::day_22::run_b() {
-        eprintln!("Error running day 22 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_23::day_23::run_a() {
-        eprintln!("Error running day 23 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
-
-    let start = Instant::now();
-    if let Err(e) = day_23::day_23::run_b() {
-        eprintln!("Error running day 23 part B: {:?}", e);
-    }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
-
-    let start = Instant::now();
-    if let Err(e) = day_24::day_24::run_a() {
-        eprintln!("Error running day 24 part A: {:?}", e);
+fn main() {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Run(options) => {
+            if let Some(input) = &options.input {
+                if let Err(e) = utils::input::set_input_path(input.clone()) {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                }
+            }
+            run_parts(&options);
+        }
+        Command::Check(options) => match runner::check::check(&options) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Error checking budgets: {}", e);
+                process::exit(2);
+            }
+        },
+        Command::Batch(options) => match runner::batch::batch(&options) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Error running day {} in batch: {}", options.day, e);
+                process::exit(1);
+            }
+        },
+        Command::Watch(options) => {
+            if let Err(e) = runner::watch::watch(&options) {
+                eprintln!("Error watching day {}: {}", options.day, e);
+                process::exit(1);
+            }
+        }
+        Command::Render(options) => match runner::render::render_day(&options) {
+            Ok(path) => println!("Rendered day {} to {}", options.day, path.display()),
+            Err(e) => {
+                eprintln!("Error rendering day {}: {}", options.day, e);
+                process::exit(1);
+            }
+        },
+        Command::Animate(options) => {
+            if let Err(e) = runner::animate::animate_day(&options) {
+                eprintln!("Error animating day {}: {}", options.day, e);
+                process::exit(1);
+            }
+        }
+        Command::Repl(options) => {
+            if let Err(e) = runner::repl::repl_day(&options) {
+                eprintln!("Error in the day {} repl: {}", options.day, e);
+                process::exit(1);
+            }
+        }
+        Command::Snapshot(options) => match runner::snapshot::snapshot_day(&options) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Error snapshotting day {}: {}", options.day, e);
+                process::exit(1);
+            }
+        },
+        Command::Generate(options) => match runner::generate::generate_day(&options) {
+            Ok(path) => println!("Generated day {} input at {}", options.day, path.display()),
+            Err(e) => {
+                eprintln!("Error generating day {} input: {}", options.day, e);
+                process::exit(1);
+            }
+        },
+        Command::Reconcile(options) => match runner::reconcile::reconcile(&options) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("Error reconciling {}: {}", options.input.display(), e);
+                process::exit(1);
+            }
+        },
+        Command::Report(options) => {
+            let mut out = io::BufWriter::new(io::stdout().lock());
+            if let Err(e) = runner::report::report_day(&options, &mut out).and_then(|()| out.flush()) {
+                eprintln!("Error reporting on day {}: {}", options.day, e);
+                process::exit(1);
+            }
+        }
     }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
+}
 
-    let start = Instant::now();
-    if let Err(e) = day_24::day_24::run_b() {
-        eprintln!("Error running day 24 part B: {:?}", e);
+fn run_parts(options: &RunOptions) {
+    let parts: Vec<_> = match options.day {
+        Some(day) => parts::parts_for_day(day),
+        None => parts::all_parts(),
     }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
+    .into_iter()
+    .filter(|part| options.part.is_none_or(|only| part.part == only))
+    .collect();
 
-    let start = Instant::now();
-    if let Err(e) = day_25::day_25::run_a() {
-        eprintln!("Error running day 25 part A: {:?}", e);
-    }
-    let duration_a = start.elapsed();
-    println!("run_a took: {:?}", duration_a);
+    let start_total = Instant::now();
+    let mut timed_out = Vec::new();
 
-    let start = Instant::now();
-    if let Err(e) = day_25::day_25::run_b() {
-        eprintln!("Error running day 25 part B: {:?}", e);
+    for part in &parts {
+        if let (Outcome::TimedOut, _) = parts::run_part(part, options.timeout) {
+            timed_out.push(format!("day {} part {}", part.day, part.part));
+        }
     }
-    let duration_b = start.elapsed();
-    println!("run_b took: {:?}", duration_b);
 
     let duration_total = start_total.elapsed();
     println!("Total time: {:?}", duration_total);
-    println!("Average time per part: {:?}", duration_total / 49);
+    println!("Average time per part: {:?}", duration_total / parts.len().max(1) as u32);
+
+    if !timed_out.is_empty() {
+        println!("Timed out: {}", timed_out.join(", "));
+    }
 }
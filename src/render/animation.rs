@@ -0,0 +1,48 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::time::Duration;
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, RgbImage};
+
+use crate::render::text::with_caption;
+
+// Writes an animated GIF one frame at a time, so a simulation never has to keep all of its frames around
+pub struct GifWriter {
+    encoder: GifEncoder<BufWriter<File>>,
+    delay: Delay,
+    // drawn above every frame
+    caption: Option<String>,
+}
+
+impl GifWriter {
+    pub fn create<P: AsRef<Path>>(path: P, frame_delay: Duration) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        // speed trades colour quantization quality for encoding time, 10 is plenty for grids of flat colours
+        let mut encoder = GifEncoder::new_with_speed(file, 10);
+        encoder.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+
+        Ok(GifWriter {
+            encoder,
+            delay: Delay::from_saturating_duration(frame_delay),
+            caption: None,
+        })
+    }
+
+    pub fn with_caption(mut self, caption: Option<String>) -> Self {
+        self.caption = caption;
+        self
+    }
+
+    pub fn add_frame(&mut self, image: &RgbImage) -> io::Result<()> {
+        let image = match &self.caption {
+            Some(caption) => with_caption(image, caption),
+            None => image.clone(),
+        };
+        let rgba = DynamicImage::ImageRgb8(image).into_rgba8();
+        self.encoder
+            .encode_frame(Frame::from_parts(rgba, 0, 0, self.delay))
+            .map_err(io::Error::other)
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use image::{Rgb, RgbImage};
+
+use crate::render::overlay::Overlay;
+use crate::render::palette::{blend, Palette};
+
+// how strongly regions and trails tint the cells underneath them
+const TINT_AMOUNT: f32 = 0.55;
+
+// Turns a grid into an image where every cell is a square of `cell_size` pixels,
+// optionally leaving a strip of `header_height` pixels above the grid for labels
+#[derive(Clone, Debug)]
+pub struct GridRenderer {
+    pub cell_size: u32,
+    pub header_height: u32,
+}
+
+impl GridRenderer {
+    pub fn new(cell_size: u32) -> Self {
+        GridRenderer {
+            cell_size: cell_size.max(1),
+            header_height: 0,
+        }
+    }
+
+    pub fn with_header(mut self, header_height: u32) -> Self {
+        self.header_height = header_height;
+        self
+    }
+
+    // an image big enough for a grid of the given size, filled with a single colour
+    pub fn blank(&self, width: usize, height: usize, color: Rgb<u8>) -> RgbImage {
+        RgbImage::from_pixel(
+            width as u32 * self.cell_size,
+            height as u32 * self.cell_size + self.header_height,
+            color,
+        )
+    }
+
+    // renders any grid, the closure decides the colour of each cell
+    pub fn render<T, F>(&self, grid: &[Vec<T>], color_of: F, overlays: &[Overlay]) -> RgbImage
+    where
+        F: Fn(&T) -> Rgb<u8>,
+    {
+        let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut image = self.blank(width, grid.len(), Rgb([0, 0, 0]));
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                self.fill_cell(&mut image, x, y, color_of(cell));
+            }
+        }
+
+        for overlay in overlays {
+            self.draw_overlay(&mut image, overlay);
+        }
+
+        image
+    }
+
+    pub fn render_chars(&self, grid: &[Vec<char>], palette: &Palette, overlays: &[Overlay]) -> RgbImage {
+        self.render(grid, |&c| palette.color(c), overlays)
+    }
+
+    pub fn fill_cell(&self, image: &mut RgbImage, x: usize, y: usize, color: Rgb<u8>) {
+        let (left, top) = self.cell_origin(x, y);
+        fill_rect(image, left, top, self.cell_size, self.cell_size, color);
+    }
+
+    pub fn draw_overlay(&self, image: &mut RgbImage, overlay: &Overlay) {
+        match overlay {
+            Overlay::Path { cells, color } => {
+                let thickness = (self.cell_size / 4).max(1);
+                for pair in cells.windows(2) {
+                    self.draw_line(image, self.cell_center(pair[0]), self.cell_center(pair[1]), thickness, *color);
+                }
+                // a single cell path still deserves to be seen
+                if let [only] = cells.as_slice() {
+                    let (x, y) = self.cell_center(*only);
+                    self.draw_line(image, (x, y), (x, y), thickness, *color);
+                }
+            }
+            Overlay::Region { cells, color } => {
+                self.tint_cells(image, cells, *color);
+                self.outline_cells(image, cells, *color);
+            }
+            Overlay::Trail { cells, color } => self.tint_cells(image, cells, *color),
+            Overlay::Markers { cells, color } => {
+                let radius = (self.cell_size as i64 / 3).max(1);
+                for &cell in cells {
+                    self.draw_dot(image, self.cell_center(cell), radius, *color);
+                }
+            }
+        }
+    }
+
+    fn cell_origin(&self, x: usize, y: usize) -> (u32, u32) {
+        (x as u32 * self.cell_size, y as u32 * self.cell_size + self.header_height)
+    }
+
+    fn cell_center(&self, (x, y): (usize, usize)) -> (i64, i64) {
+        let (left, top) = self.cell_origin(x, y);
+        let half = self.cell_size as i64 / 2;
+        (left as i64 + half, top as i64 + half)
+    }
+
+    fn tint_cells(&self, image: &mut RgbImage, cells: &[(usize, usize)], color: Rgb<u8>) {
+        for &(x, y) in cells {
+            let (left, top) = self.cell_origin(x, y);
+            for py in top..top + self.cell_size {
+                for px in left..left + self.cell_size {
+                    if let Some(pixel) = image.get_pixel_mut_checked(px, py) {
+                        *pixel = blend(*pixel, color, TINT_AMOUNT);
+                    }
+                }
+            }
+        }
+    }
+
+    // draws a border along every cell edge that doesn't have another cell of the region on the other side
+    fn outline_cells(&self, image: &mut RgbImage, cells: &[(usize, usize)], color: Rgb<u8>) {
+        let cell_set: HashSet<(usize, usize)> = cells.iter().copied().collect();
+        let thickness = (self.cell_size / 8).max(1);
+        let size = self.cell_size;
+
+        for &(x, y) in cells {
+            let (left, top) = self.cell_origin(x, y);
+
+            if y == 0 || !cell_set.contains(&(x, y - 1)) {
+                fill_rect(image, left, top, size, thickness, color);
+            }
+            if !cell_set.contains(&(x, y + 1)) {
+                fill_rect(image, left, top + size - thickness, size, thickness, color);
+            }
+            if x == 0 || !cell_set.contains(&(x - 1, y)) {
+                fill_rect(image, left, top, thickness, size, color);
+            }
+            if !cell_set.contains(&(x + 1, y)) {
+                fill_rect(image, left + size - thickness, top, thickness, size, color);
+            }
+        }
+    }
+
+    // steps along the line one pixel at a time, stamping a square of the given thickness at each step
+    fn draw_line(&self, image: &mut RgbImage, from: (i64, i64), to: (i64, i64), thickness: u32, color: Rgb<u8>) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+        let half = thickness as i64 / 2;
+
+        for step in 0..=steps {
+            let x = from.0 + (to.0 - from.0) * step / steps;
+            let y = from.1 + (to.1 - from.1) * step / steps;
+            if x - half >= 0 && y - half >= 0 {
+                fill_rect(image, (x - half) as u32, (y - half) as u32, thickness, thickness, color);
+            }
+        }
+    }
+
+    fn draw_dot(&self, image: &mut RgbImage, (cx, cy): (i64, i64), radius: i64, color: Rgb<u8>) {
+        for y in cy - radius..=cy + radius {
+            for x in cx - radius..=cx + radius {
+                let inside = (x - cx).pow(2) + (y - cy).pow(2) <= radius.pow(2);
+                if inside && x >= 0 && y >= 0 {
+                    if let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) {
+                        *pixel = color;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// fills a rectangle, clipping anything that falls outside the image
+pub fn fill_rect(image: &mut RgbImage, left: u32, top: u32, width: u32, height: u32, color: Rgb<u8>) {
+    let right = (left + width).min(image.width());
+    let bottom = (top + height).min(image.height());
+
+    for y in top..bottom {
+        for x in left..right {
+            image.put_pixel(x, y, color);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod animation;
+pub mod grid_renderer;
+pub mod overlay;
+pub mod palette;
+pub mod terminal;
+pub mod text;
@@ -0,0 +1,14 @@
+use image::Rgb;
+
+// Something drawn on top of a rendered grid. Cells are (x, y), i.e. (column, row)
+#[derive(Clone, Debug)]
+pub enum Overlay {
+    // a line joining the centre of each cell in order, e.g. a route through a maze
+    Path { cells: Vec<(usize, usize)>, color: Rgb<u8> },
+    // cells tinted with the colour and outlined where the region ends, e.g. a garden plot
+    Region { cells: Vec<(usize, usize)>, color: Rgb<u8> },
+    // cells tinted with the colour without an outline, e.g. everywhere the guard has walked
+    Trail { cells: Vec<(usize, usize)>, color: Rgb<u8> },
+    // a dot in the middle of each cell, e.g. antinodes
+    Markers { cells: Vec<(usize, usize)>, color: Rgb<u8> },
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use image::Rgb;
+
+pub const BACKGROUND: Rgb<u8> = Rgb([15, 15, 35]);
+pub const WALL: Rgb<u8> = Rgb([90, 90, 110]);
+pub const WHITE: Rgb<u8> = Rgb([255, 255, 255]);
+pub const GOLD: Rgb<u8> = Rgb([255, 215, 0]);
+pub const GREEN: Rgb<u8> = Rgb([0, 204, 0]);
+pub const RED: Rgb<u8> = Rgb([220, 50, 50]);
+pub const BLUE: Rgb<u8> = Rgb([80, 140, 255]);
+
+// Maps the characters of a grid to colours, anything not in the palette gets the fallback colour
+#[derive(Clone, Debug)]
+pub struct Palette {
+    colors: HashMap<char, Rgb<u8>>,
+    fallback: Rgb<u8>,
+}
+
+impl Palette {
+    pub fn new(fallback: Rgb<u8>) -> Self {
+        Palette {
+            colors: HashMap::new(),
+            fallback,
+        }
+    }
+
+    // walls, floor and the start and end markers, as most of the maze puzzles draw them
+    pub fn maze() -> Self {
+        Palette::new(WHITE)
+            .with('#', WALL)
+            .with('.', BACKGROUND)
+            .with('S', GREEN)
+            .with('E', RED)
+    }
+
+    pub fn with(mut self, c: char, color: Rgb<u8>) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb<u8> {
+        *self.colors.get(&c).unwrap_or(&self.fallback)
+    }
+}
+
+// a different colour for each index, for when there are too many things (regions, frequencies) to pick colours by hand.
+// stepping the hue by the golden angle keeps neighbouring indices far apart on the colour wheel
+pub fn distinct_color(index: usize) -> Rgb<u8> {
+    let hue = (index as f32 * 137.508) % 360.0;
+    hsv_to_rgb(hue, 0.65, 0.95)
+}
+
+// linear blend between two colours, amount 0.0 is all `base` and 1.0 is all `tint`
+pub fn blend(base: Rgb<u8>, tint: Rgb<u8>, amount: f32) -> Rgb<u8> {
+    let mix = |a: u8, b: u8| (a as f32 * (1.0 - amount) + b as f32 * amount).round() as u8;
+    Rgb([mix(base[0], tint[0]), mix(base[1], tint[1]), mix(base[2], tint[2])])
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Rgb<u8> {
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = value - chroma;
+
+    let (r, g, b) = match hue as u32 {
+        0..=59 => (chroma, x, 0.0),
+        60..=119 => (x, chroma, 0.0),
+        120..=179 => (0.0, chroma, x),
+        180..=239 => (0.0, x, chroma),
+        240..=299 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let to_byte = |channel: f32| ((channel + m) * 255.0).round() as u8;
+    Rgb([to_byte(r), to_byte(g), to_byte(b)])
+}
@@ -0,0 +1,164 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_TO_END: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET: &str = "\x1b[0m";
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+// Redraws text snapshots of a simulation in place in the terminal, one frame per step.
+// The terminal only hands us whole lines, so controls are typed followed by Enter:
+// an empty line pauses / resumes, `s` steps a single frame, `+` / `-` change the speed and `q` quits
+pub struct TerminalAnimation {
+    delay: Duration,
+    paused: bool,
+    controls: Receiver<Control>,
+    controls_closed: bool,
+    frame_count: usize,
+}
+
+impl TerminalAnimation {
+    pub fn new(delay: Duration, start_paused: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        // reading stdin blocks, so it gets its own thread which stops when stdin is closed
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                let control = match line.trim() {
+                    "" | "p" => Control::TogglePause,
+                    "s" => Control::Step,
+                    "+" => Control::Faster,
+                    "-" => Control::Slower,
+                    "q" => Control::Quit,
+                    _ => continue,
+                };
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        });
+
+        print!("{}{}", HIDE_CURSOR, CLEAR_SCREEN);
+
+        TerminalAnimation {
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+            paused: start_paused,
+            controls: receiver,
+            controls_closed: false,
+            frame_count: 0,
+        }
+    }
+
+    // draws the snapshot over the previous one and waits for the next step, returns false once the viewer quits
+    pub fn show(&mut self, snapshot: &str, status: &str) -> bool {
+        self.frame_count += 1;
+        let state = if self.paused { "paused" } else { "playing" };
+
+        let mut stdout = io::stdout().lock();
+        // if stdout has gone away there's no one watching anyway
+        let _ = write!(
+            stdout,
+            "{}{}{}\n{} | frame {} | {} every {:?}{}\n[Enter] pause/resume  [s] step  [+/-] faster/slower  [q] quit\n",
+            CURSOR_HOME,
+            colorize(snapshot),
+            RESET,
+            status,
+            self.frame_count,
+            state,
+            self.delay,
+            CLEAR_TO_END,
+        );
+        let _ = stdout.flush();
+        drop(stdout);
+
+        self.wait_for_next_frame()
+    }
+
+    fn wait_for_next_frame(&mut self) -> bool {
+        let mut deadline = Instant::now() + self.delay;
+
+        loop {
+            let control = if self.controls_closed {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                return true;
+            } else if self.paused {
+                self.controls.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                self.controls.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            };
+
+            match control {
+                Ok(Control::Quit) => return false,
+                Ok(Control::Step) => {
+                    // stepping always leaves us paused on the next frame
+                    self.paused = true;
+                    return true;
+                }
+                Ok(Control::TogglePause) => {
+                    self.paused = !self.paused;
+                    deadline = Instant::now() + self.delay;
+                }
+                Ok(Control::Faster) => self.delay = (self.delay / 2).max(MIN_DELAY),
+                Ok(Control::Slower) => self.delay = (self.delay * 2).min(MAX_DELAY),
+                Err(RecvTimeoutError::Timeout) => return true,
+                Err(RecvTimeoutError::Disconnected) => {
+                    // no more input, e.g. stdin isn't a terminal, so just keep playing
+                    self.controls_closed = true;
+                    self.paused = false;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for TerminalAnimation {
+    fn drop(&mut self) {
+        print!("{}", SHOW_CURSOR);
+        let _ = io::stdout().flush();
+    }
+}
+
+// colours the characters the simulations use in their snapshots, everything else is left as is
+pub fn colorize(snapshot: &str) -> String {
+    let mut output = String::with_capacity(snapshot.len() * 2);
+    let mut current_color = "";
+
+    for c in snapshot.chars() {
+        let color = match c {
+            // walls grey, boxes yellow, the robot and guard bright red
+            '#' => "\x1b[90m",
+            'O' | '[' | ']' => "\x1b[33m",
+            '@' | '^' | 'v' | '<' | '>' => "\x1b[1;91m",
+            // visited tiles cyan, drones bright green and empty floor dimmed
+            'X' => "\x1b[36m",
+            '1'..='9' => "\x1b[1;92m",
+            '.' => "\x1b[2m",
+            _ => RESET,
+        };
+
+        if c != '\n' && color != current_color {
+            output.push_str(RESET);
+            output.push_str(color);
+            current_color = color;
+        }
+        output.push(c);
+    }
+
+    output
+}
@@ -0,0 +1,89 @@
+use std::sync::OnceLock;
+
+use image::{Rgb, RgbImage};
+use rusttype::{point, Font, Scale};
+
+use crate::render::palette::blend;
+
+static FONT_DATA: &[u8] = include_bytes!("../../assets/SpaceMono-Regular.ttf");
+static FONT: OnceLock<Font<'static>> = OnceLock::new();
+
+// Some text to draw on an image, `x` and `y` are the top left corner in pixels and `size` is the font height in pixels
+#[derive(Clone, Debug)]
+pub struct Label {
+    pub text: String,
+    pub x: i32,
+    pub y: i32,
+    pub size: f32,
+    pub color: Rgb<u8>,
+}
+
+impl Label {
+    pub fn new(text: impl Into<String>, x: i32, y: i32, size: f32, color: Rgb<u8>) -> Self {
+        Label {
+            text: text.into(),
+            x,
+            y,
+            size,
+            color,
+        }
+    }
+}
+
+// the Space Mono font bundled in assets/, parsed the first time it's needed
+pub fn bundled_font() -> &'static Font<'static> {
+    FONT.get_or_init(|| Font::try_from_bytes(FONT_DATA).expect("assets/SpaceMono-Regular.ttf is not a valid font"))
+}
+
+// glyphs are anti-aliased, so each pixel is blended with the label colour by how much of it the glyph covers.
+// anything that falls outside the image is clipped
+pub fn draw_label(image: &mut RgbImage, label: &Label) {
+    let font = bundled_font();
+    let scale = Scale::uniform(label.size);
+    let ascent = font.v_metrics(scale).ascent;
+
+    for glyph in font.layout(&label.text, scale, point(label.x as f32, label.y as f32 + ascent)) {
+        let Some(bounds) = glyph.pixel_bounding_box() else {
+            continue;
+        };
+
+        glyph.draw(|gx, gy, coverage| {
+            let x = bounds.min.x + gx as i32;
+            let y = bounds.min.y + gy as i32;
+            if x < 0 || y < 0 {
+                return;
+            }
+            if let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) {
+                *pixel = blend(*pixel, label.color, coverage);
+            }
+        });
+    }
+}
+
+// how many pixels wide the text would be at the given size, handy for lining labels up
+pub fn text_width(text: &str, size: f32) -> u32 {
+    let scale = Scale::uniform(size);
+    bundled_font()
+        .layout(text, scale, point(0.0, 0.0))
+        .last()
+        .map(|glyph| glyph.position().x + glyph.unpositioned().h_metrics().advance_width)
+        .unwrap_or(0.0)
+        .ceil() as u32
+}
+
+// a copy of the image with a strip above it holding the caption, so any render can say what it shows. the image
+// is widened if the caption doesn't fit across it
+pub fn with_caption(image: &RgbImage, caption: &str) -> RgbImage {
+    const SIZE: f32 = 16.0;
+    const PADDING: u32 = 4;
+
+    let strip = SIZE as u32 + 2 * PADDING;
+    let width = image.width().max(text_width(caption, SIZE) + 2 * PADDING);
+    let mut captioned = RgbImage::from_pixel(width, image.height() + strip, Rgb([0, 0, 0]));
+    image::imageops::replace(&mut captioned, image, 0, strip as i64);
+    draw_label(
+        &mut captioned,
+        &Label::new(caption, PADDING as i32, PADDING as i32, SIZE, Rgb([255, 255, 255])),
+    );
+    captioned
+}
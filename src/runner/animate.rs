@@ -0,0 +1,29 @@
+use std::io;
+use std::time::Duration;
+
+use crate::render::terminal::TerminalAnimation;
+
+pub struct AnimateOptions {
+    pub day: u8,
+    // how long each frame stays on screen, can be changed while it plays
+    pub delay: Duration,
+    pub paused: bool,
+}
+
+// plays the day's simulation in the terminal until it finishes or the viewer quits
+pub fn animate_day(options: &AnimateOptions) -> io::Result<()> {
+    let animate: fn(&mut TerminalAnimation) -> io::Result<()> = match options.day {
+        6 => crate::day_6::day_6::animate,
+        14 => crate::day_14::day_14::animate,
+        15 => crate::day_15::day_15::animate,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("day {} doesn't have a simulation to animate", options.day),
+            ))
+        }
+    };
+
+    let mut animation = TerminalAnimation::new(options.delay, options.paused);
+    animate(&mut animation)
+}
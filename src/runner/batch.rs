@@ -0,0 +1,185 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::runner::output::{parse_parts, PartRun};
+use crate::utils::report::format_table;
+
+pub struct BatchOptions {
+    pub day: u8,
+    // a directory of input files, or a pattern like inputs/day_7/*.txt
+    pub inputs: String,
+    pub timeout: Option<Duration>,
+}
+
+// how solving the day went for one input file
+struct InputRun {
+    name: String,
+    parts: Result<Vec<PartRun>, String>,
+    elapsed: Duration,
+}
+
+impl InputRun {
+    fn failed(&self) -> bool {
+        match &self.parts {
+            Ok(parts) => parts.iter().any(part_failed),
+            Err(_) => true,
+        }
+    }
+}
+
+// solves the day for every input at once and prints a table of the answers, returning whether they all succeeded
+pub fn batch(options: &BatchOptions) -> io::Result<bool> {
+    let inputs = find_inputs(&options.inputs)?;
+    if inputs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no input files match {}", options.inputs),
+        ));
+    }
+
+    println!("Solving day {} for {} inputs...", options.day, inputs.len());
+
+    // every input gets its own process, so a panic or a runaway part only takes that input down with it
+    let runs: Vec<InputRun> = inputs.par_iter().map(|input| run_input(options, input)).collect();
+
+    print_table(&runs);
+    Ok(!runs.iter().any(InputRun::failed))
+}
+
+fn find_inputs(spec: &str) -> io::Result<Vec<PathBuf>> {
+    let path = Path::new(spec);
+
+    let (dir, pattern) = if path.is_dir() {
+        (path, "*")
+    } else if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    } else {
+        let pattern = path.file_name().and_then(|name| name.to_str()).unwrap_or("*");
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        (dir, pattern)
+    };
+
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| wildcard_match(pattern, name));
+        if path.is_file() && matches {
+            inputs.push(path);
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+// `*` matches any run of characters and `?` any single one, like a shell glob within one directory
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // matches[j] is whether the pattern so far matches the first j characters of the name
+    let mut matches = vec![false; name.len() + 1];
+    matches[0] = true;
+
+    for &p in &pattern {
+        let mut next = vec![false; name.len() + 1];
+        for j in 0..=name.len() {
+            next[j] = match p {
+                '*' => matches[j] || (j > 0 && next[j - 1]),
+                '?' => j > 0 && matches[j - 1],
+                c => j > 0 && matches[j - 1] && name[j - 1] == c,
+            };
+        }
+        matches = next;
+    }
+
+    matches[name.len()]
+}
+
+fn run_input(options: &BatchOptions, input: &Path) -> InputRun {
+    let name = input.display().to_string();
+    let start = Instant::now();
+    let parts = solve_input(options, input);
+
+    InputRun {
+        name,
+        parts,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn solve_input(options: &BatchOptions, input: &Path) -> Result<Vec<PartRun>, String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let mut command = Command::new(exe);
+    command.args(["--day", &options.day.to_string()]);
+    command.arg("--input").arg(input);
+    if let Some(timeout) = options.timeout {
+        command.args(["--timeout", &timeout.as_millis().to_string()]);
+    }
+
+    let output = command.output().map_err(|e| e.to_string())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let parts = parse_parts(&stdout, &stderr, options.day);
+
+    if parts.is_empty() {
+        // usually a panic, its message is on the line after "thread '...' panicked at ..."
+        let lines: Vec<&str> = stderr.lines().collect();
+        let reason = match lines.iter().position(|line| line.contains("panicked at")) {
+            Some(i) => format!("panicked: {}", lines.get(i + 1).unwrap_or(&"").trim()),
+            None => lines
+                .iter()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map_or("no output".to_string(), |line| line.trim().to_string()),
+        };
+        return Err(format!("{} ({})", reason, output.status));
+    }
+
+    Ok(parts)
+}
+
+// a part that returned an error has the error as its last line of output
+fn part_failed(part: &PartRun) -> bool {
+    part.took.ends_with("(timed out)") || part.answer().starts_with("Error running day ")
+}
+
+fn part_cell(part: Option<&PartRun>) -> String {
+    match part {
+        None => "-".to_string(),
+        Some(part) if part.took.ends_with("(timed out)") => "timed out".to_string(),
+        Some(part) => part.answer().to_string(),
+    }
+}
+
+fn print_table(runs: &[InputRun]) {
+    let mut rows = vec![vec![
+        "Input".to_string(),
+        "Part A".to_string(),
+        "Part B".to_string(),
+        "Time".to_string(),
+    ]];
+
+    for run in runs {
+        let (a, b) = match &run.parts {
+            Ok(parts) => (
+                part_cell(parts.iter().find(|part| part.part == 'A')),
+                part_cell(parts.iter().find(|part| part.part == 'B')),
+            ),
+            Err(e) => (format!("failed: {}", e), "-".to_string()),
+        };
+        let status = if run.failed() { " FAILED" } else { "" };
+        rows.push(vec![run.name.clone(), a, b, format!("{:.2?}{}", run.elapsed, status)]);
+    }
+
+    print!("{}", format_table(&rows));
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::runner::output::parse_parts;
+use crate::runner::parts::{self, Outcome, Part};
+
+// parts that go this many times over their budget are stopped rather than waited on
+const HARD_LIMIT_FACTOR: u32 = 10;
+
+pub struct CheckOptions {
+    // None checks every day
+    pub day: Option<u8>,
+    pub budgets: PathBuf,
+    // how many more times to run a part that went over budget, in case it was just noise
+    pub retries: u32,
+}
+
+// How long each part may take, read from a file like
+//
+//     # milliseconds, the most specific line wins
+//     default: 1000
+//     14: 5000
+//     14b: 60000
+struct Budgets {
+    default: Option<Duration>,
+    days: HashMap<u8, Duration>,
+    parts: HashMap<(u8, char), Duration>,
+}
+
+impl Budgets {
+    fn parse(content: &str) -> Result<Self, String> {
+        let mut budgets = Budgets {
+            default: None,
+            days: HashMap::new(),
+            parts: HashMap::new(),
+        };
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = || format!("line {}: expected `<day>[a|b]: <ms>` or `default: <ms>`, got {}", i + 1, line);
+            let (key, millis) = line.split_once(':').ok_or_else(invalid)?;
+            let budget = Duration::from_millis(millis.trim().parse().map_err(|_| invalid())?);
+            let key = key.trim().to_ascii_lowercase();
+
+            if key == "default" {
+                budgets.default = Some(budget);
+            } else if let Some(day) = key.strip_suffix(['a', 'b']) {
+                let part = key.chars().last().unwrap().to_ascii_uppercase();
+                budgets.parts.insert((day.parse().map_err(|_| invalid())?, part), budget);
+            } else {
+                budgets.days.insert(key.parse().map_err(|_| invalid())?, budget);
+            }
+        }
+
+        Ok(budgets)
+    }
+
+    fn budget_for(&self, part: &Part) -> Option<Duration> {
+        self.parts
+            .get(&(part.day, part.part))
+            .or_else(|| self.days.get(&part.day))
+            .copied()
+            .or(self.default)
+    }
+}
+
+enum Verdict {
+    WithinBudget,
+    OverBudget { best: Duration, attempts: u32 },
+    Failed(String),
+    NoBudget,
+}
+
+// runs every part against its budget and prints a summary, returning whether they all made it
+pub fn check(options: &CheckOptions) -> io::Result<bool> {
+    let content = fs::read_to_string(&options.budgets).map_err(|e| {
+        io::Error::new(e.kind(), format!("could not read {}: {}", options.budgets.display(), e))
+    })?;
+    let budgets = Budgets::parse(&content).map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", options.budgets.display(), e))
+    })?;
+
+    let parts = match options.day {
+        Some(day) => parts::parts_for_day(day),
+        None => parts::all_parts(),
+    };
+
+    let mut results = Vec::new();
+    for part in &parts {
+        let budget = budgets.budget_for(part);
+        let verdict = match budget {
+            Some(budget) => check_part(part, budget, options.retries),
+            None => Verdict::NoBudget,
+        };
+        results.push((part, budget, verdict));
+    }
+
+    println!("\n=== Budget check ===");
+    let mut passed = 0;
+    let mut problems = Vec::new();
+
+    for (part, budget, verdict) in &results {
+        let name = format!("day {} part {}", part.day, part.part);
+        match verdict {
+            Verdict::WithinBudget => passed += 1,
+            Verdict::NoBudget => println!("{}: no budget, skipped", name),
+            Verdict::OverBudget { best, attempts } => {
+                let line = format!(
+                    "{}: took {:?}, over its budget of {:?} (best of {} runs)",
+                    name,
+                    best,
+                    budget.unwrap_or_default(),
+                    attempts
+                );
+                problems.push(line);
+            }
+            Verdict::Failed(e) => problems.push(format!("{}: failed: {}", name, e)),
+        }
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    println!("{} of {} parts within budget", passed, results.len());
+
+    Ok(problems.is_empty())
+}
+
+// keeps the fastest of up to 1 + retries runs, stopping as soon as one is within budget
+fn check_part(part: &Part, budget: Duration, retries: u32) -> Verdict {
+    let mut best: Option<Duration> = None;
+    let mut attempts = 0;
+
+    for _ in 0..=retries {
+        attempts += 1;
+        let (outcome, duration) = run_isolated(part, budget * HARD_LIMIT_FACTOR);
+
+        match outcome {
+            Outcome::Failed(e) => return Verdict::Failed(e),
+            // way over budget, another run won't change that
+            Outcome::TimedOut => {
+                return Verdict::OverBudget {
+                    best: duration,
+                    attempts,
+                }
+            }
+            Outcome::Solved if duration <= budget => return Verdict::WithinBudget,
+            Outcome::Solved => best = Some(best.map_or(duration, |best| best.min(duration))),
+        }
+    }
+
+    Verdict::OverBudget {
+        best: best.unwrap_or_default(),
+        attempts,
+    }
+}
+
+// Runs the part in a child process, killing it if it's still going at the hard limit. Most parts never check their
+// cancellation token, so on a thread in this process a runaway part would carry on using a core while the parts
+// after it were timed
+fn run_isolated(part: &Part, hard_limit: Duration) -> (Outcome, Duration) {
+    let start = Instant::now();
+    let spawned = env::current_exe().and_then(|exe| {
+        Command::new(exe)
+            .args(["--day", &part.day.to_string(), "--part", &part.part.to_string()])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+    });
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => return (Outcome::Failed(format!("could not start the part: {}", e)), start.elapsed()),
+    };
+
+    // read as it's written, so a part that prints a lot can't fill the pipe and stall
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let finished = wait_until(&mut child, start + hard_limit);
+    let elapsed = start.elapsed();
+    if !finished {
+        let _ = child.kill();
+        let _ = child.wait();
+        eprintln!("Day {} part {} stopped after {:?}", part.day, part.part, elapsed);
+        return (Outcome::TimedOut, elapsed);
+    }
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let parts = parse_parts(&stdout, &stderr, part.day);
+    let Some(run) = parts.iter().find(|run| run.part == part.part) else {
+        eprint!("{}", stderr);
+        return (Outcome::Failed("no output, it probably panicked".to_string()), elapsed);
+    };
+
+    // just the part's own output, not the child's totals
+    for line in &run.output {
+        println!("{}", line);
+    }
+    println!("run_{} took: {}", part.part.to_ascii_lowercase(), run.took);
+
+    let error_prefix = format!("Error running day {} part {}: ", part.day, part.part);
+    let outcome = match run.answer().strip_prefix(&error_prefix) {
+        Some(e) => Outcome::Failed(e.to_string()),
+        None => Outcome::Solved,
+    };
+    // the part's own timing leaves out starting the process
+    (outcome, run.duration().unwrap_or(elapsed))
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+// whether the child exited before the deadline
+fn wait_until(child: &mut Child, deadline: Instant) -> bool {
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return true,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+            // can't tell, so treat it like it's still running and stop it
+            _ => return false,
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::runner::animate::AnimateOptions;
+use crate::runner::batch::BatchOptions;
+use crate::runner::check::CheckOptions;
+use crate::runner::generate::GenerateOptions;
+use crate::runner::reconcile::ReconcileOptions;
+use crate::runner::render::RenderOptions;
+use crate::runner::report::ReportOptions;
+use crate::runner::repl::ReplOptions;
+use crate::runner::snapshot::SnapshotOptions;
+use crate::runner::watch::WatchOptions;
+use crate::utils::read_as_columns::RaggedRows;
+use crate::utils::report::ReportFormat;
+
+pub const USAGE: &str = "Usage:
+    advent-of-code-2024-in-rust [options]                    run every part of every day
+    advent-of-code-2024-in-rust --day <day> [options]        run both parts of a single day
+    advent-of-code-2024-in-rust batch <day> <dir|glob> [options]
+                                                             solve a day for every input file in a directory or matching a pattern
+    advent-of-code-2024-in-rust check [options]              run every part against its time budget, failing if any go over
+    advent-of-code-2024-in-rust watch <day> [options]        re-run a day whenever its input changes
+    advent-of-code-2024-in-rust render <day> [options]       draw the day's grid (days 6, 8, 10, 12, 16 and 18)
+    advent-of-code-2024-in-rust animate <day> [options]      play the day's simulation in the terminal (days 6, 14 and 15)
+    advent-of-code-2024-in-rust repl <day> [--input <file>]     load the day's puzzle state and inspect it interactively (days 15, 17 and 23)
+    advent-of-code-2024-in-rust snapshot <day> <steps> [options]
+                                                             compare the day's grid after <steps> steps with its golden file (days 14 and 15)
+    advent-of-code-2024-in-rust generate <day> <size> [options]
+                                                             write a made up input with <size> entries, for timing on huge inputs (day 1)
+    advent-of-code-2024-in-rust reconcile <file> [options]   compare any number of lists side by side with day 1's distance and similarity
+    advent-of-code-2024-in-rust report <day> [options]       explain the day's answer line by line (days 2, 3 and 5)
+
+Options:
+    --part <a|b>           only run one part of each day
+    --timeout <ms>         give up on any part that runs longer than this and move on to the next
+    --input <file>         read this file instead of the day's input.txt
+
+Batch options:
+    --timeout <ms>         as above, for each input
+
+Check options:
+    --day <day>            only check a single day
+    --budgets <file>       the budget file, defaults to budgets.txt
+    --retries <n>          how many more times to run a part that went over budget, defaults to 2
+
+Watch options:
+    --source               also re-run (and rebuild) when the day's .rs files change
+    --interval <ms>        how often to poll for changes, defaults to 500
+    --timeout <ms>         as above
+
+Render options:
+    --out <dir>            where to write the image, defaults to renders
+    --cell-size <px>       size of each grid cell, defaults to 8
+    --gif                  animate the simulation as a GIF instead (day 6)
+    --caption <text>       draw a line of text above the grid, e.g. a legend for the colours
+
+Animate options:
+    --delay <ms>           how long each frame is shown, defaults to 100
+    --paused               start paused, stepping one frame at a time
+    while playing, type a command and press Enter: nothing to pause/resume, s to step, + / - to change speed, q to quit
+
+Snapshot options:
+    --input <file>         simulate this input instead of the day's input_example.txt
+    --accept               replace the golden file when the grid has changed
+
+Generate options:
+    --out <file>           where to write the input, defaults to generated/day_<day>_<size>.txt
+    --seed <n>             the same seed always gives the same input, defaults to 1
+
+Reconcile options:
+    --delimiter <text>     what separates the lists on each line, defaults to any whitespace
+    --header               the first line names the lists
+    --ragged <policy>      lines with fewer values than the others: pad (the default) leaves the missing ones empty,
+                           truncate only compares the lists on the first line, error stops with the line number
+
+Report options:
+    --format <table|json>  defaults to table
+    --input <file>         as above";
+
+pub struct RunOptions {
+    // None runs every day
+    pub day: Option<u8>,
+    // None runs both parts
+    pub part: Option<char>,
+    pub timeout: Option<Duration>,
+    pub input: Option<String>,
+}
+
+pub enum Command {
+    Run(RunOptions),
+    Batch(BatchOptions),
+    Check(CheckOptions),
+    Watch(WatchOptions),
+    Render(RenderOptions),
+    Animate(AnimateOptions),
+    Snapshot(SnapshotOptions),
+    Repl(ReplOptions),
+    Generate(GenerateOptions),
+    Reconcile(ReconcileOptions),
+    Report(ReportOptions),
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("batch") => {
+            args.next();
+            let mut options = BatchOptions {
+                day: parse_day(args.next())?,
+                inputs: args.next().ok_or("Missing input directory or pattern")?,
+                timeout: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--timeout" => {
+                        let millis = parse_number(args.next(), "--timeout")?;
+                        options.timeout = Some(Duration::from_millis(millis));
+                    }
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Batch(options))
+        }
+        Some("check") => {
+            args.next();
+            let mut options = CheckOptions {
+                day: None,
+                budgets: PathBuf::from("budgets.txt"),
+                retries: 2,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--day" => options.day = Some(parse_day(args.next())?),
+                    "--budgets" => options.budgets = PathBuf::from(args.next().ok_or("Missing value for --budgets")?),
+                    "--retries" => options.retries = parse_number(args.next(), "--retries")? as u32,
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Check(options))
+        }
+        Some("watch") => {
+            args.next();
+            let mut options = WatchOptions {
+                day: parse_day(args.next())?,
+                watch_source: false,
+                interval: Duration::from_millis(500),
+                timeout: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--source" => options.watch_source = true,
+                    "--interval" => {
+                        let millis = parse_number(args.next(), "--interval")?;
+                        options.interval = Duration::from_millis(millis);
+                    }
+                    "--timeout" => {
+                        let millis = parse_number(args.next(), "--timeout")?;
+                        options.timeout = Some(Duration::from_millis(millis));
+                    }
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Watch(options))
+        }
+        Some("render") => {
+            args.next();
+            let mut options = RenderOptions {
+                day: parse_day(args.next())?,
+                output_dir: PathBuf::from("renders"),
+                cell_size: 8,
+                animate: false,
+                caption: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--out" => options.output_dir = PathBuf::from(args.next().ok_or("Missing value for --out")?),
+                    "--cell-size" => options.cell_size = parse_number(args.next(), "--cell-size")? as u32,
+                    "--gif" => options.animate = true,
+                    "--caption" => options.caption = Some(args.next().ok_or("Missing value for --caption")?),
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Render(options))
+        }
+        Some("animate") => {
+            args.next();
+            let mut options = AnimateOptions {
+                day: parse_day(args.next())?,
+                delay: Duration::from_millis(100),
+                paused: false,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--delay" => options.delay = Duration::from_millis(parse_number(args.next(), "--delay")?),
+                    "--paused" => options.paused = true,
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Animate(options))
+        }
+        Some("repl") => {
+            args.next();
+            let mut options = ReplOptions {
+                day: parse_day(args.next())?,
+                input: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--input" => options.input = Some(args.next().ok_or("Missing value for --input")?),
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Repl(options))
+        }
+        Some("snapshot") => {
+            args.next();
+            let mut options = SnapshotOptions {
+                day: parse_day(args.next())?,
+                steps: parse_number(args.next(), "<steps>")? as usize,
+                input: None,
+                accept: false,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--input" => options.input = Some(PathBuf::from(args.next().ok_or("Missing value for --input")?)),
+                    "--accept" => options.accept = true,
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Snapshot(options))
+        }
+        Some("generate") => {
+            args.next();
+            let mut options = GenerateOptions {
+                day: parse_day(args.next())?,
+                size: parse_number(args.next(), "<size>")?,
+                seed: 1,
+                output: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--out" => options.output = Some(PathBuf::from(args.next().ok_or("Missing value for --out")?)),
+                    "--seed" => options.seed = parse_number(args.next(), "--seed")?,
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Generate(options))
+        }
+        Some("reconcile") => {
+            args.next();
+            let mut options = ReconcileOptions {
+                input: PathBuf::from(args.next().ok_or("Missing input file")?),
+                delimiter: None,
+                header: false,
+                ragged: RaggedRows::Pad,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--delimiter" => options.delimiter = Some(args.next().ok_or("Missing value for --delimiter")?),
+                    "--header" => options.header = true,
+                    "--ragged" => {
+                        options.ragged = match args.next().as_deref() {
+                            Some("pad") => RaggedRows::Pad,
+                            Some("truncate") => RaggedRows::Truncate,
+                            Some("error") => RaggedRows::Error,
+                            other => return Err(format!("Invalid value for --ragged: {}", other.unwrap_or(""))),
+                        }
+                    }
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Reconcile(options))
+        }
+        Some("report") => {
+            args.next();
+            let mut options = ReportOptions {
+                day: parse_day(args.next())?,
+                input: None,
+                format: ReportFormat::Table,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        options.format = match args.next().as_deref() {
+                            Some("table") => ReportFormat::Table,
+                            Some("json") => ReportFormat::Json,
+                            other => return Err(format!("Invalid value for --format: {}", other.unwrap_or(""))),
+                        }
+                    }
+                    "--input" => options.input = Some(args.next().ok_or("Missing value for --input")?),
+                    _ => return Err(format!("Unexpected argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Report(options))
+        }
+        _ => {
+            let mut options = RunOptions {
+                day: None,
+                part: None,
+                timeout: None,
+                input: None,
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--day" => options.day = Some(parse_day(args.next())?),
+                    "--part" => {
+                        options.part = match args.next().as_deref() {
+                            Some("a" | "A") => Some('A'),
+                            Some("b" | "B") => Some('B'),
+                            other => return Err(format!("Invalid value for --part: {}", other.unwrap_or(""))),
+                        }
+                    }
+                    "--timeout" => {
+                        let millis = parse_number(args.next(), "--timeout")?;
+                        options.timeout = Some(Duration::from_millis(millis));
+                    }
+                    "--input" => options.input = Some(args.next().ok_or("Missing value for --input")?),
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+
+            Ok(Command::Run(options))
+        }
+    }
+}
+
+fn parse_day(arg: Option<String>) -> Result<u8, String> {
+    let arg = arg.ok_or("Missing day number")?;
+    match arg.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day: {} (expected 1 to 25)", arg)),
+    }
+}
+
+fn parse_number(arg: Option<String>, flag: &str) -> Result<u64, String> {
+    let arg = arg.ok_or(format!("Missing value for {}", flag))?;
+    arg.parse::<u64>()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, arg))
+}
@@ -0,0 +1,35 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub struct GenerateOptions {
+    pub day: u8,
+    // how many entries to generate, what an entry is depends on the day
+    pub size: u64,
+    pub seed: u64,
+    // None writes to generated/day_<day>_<size>.txt
+    pub output: Option<PathBuf>,
+}
+
+// writes a large made up input for a day, returning where it went
+pub fn generate_day(options: &GenerateOptions) -> io::Result<PathBuf> {
+    let path = match &options.output {
+        Some(path) => path.clone(),
+        None => PathBuf::from("generated").join(format!("day_{}_{}.txt", options.day, options.size)),
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    match options.day {
+        1 => crate::day_1::day_1::generate(&path, options.size, options.seed)?,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("day {} doesn't have an input generator", options.day),
+            ))
+        }
+    }
+
+    Ok(path)
+}
\ No newline at end of file
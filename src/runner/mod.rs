@@ -0,0 +1,13 @@
+pub mod animate;
+pub mod batch;
+pub mod check;
+pub mod cli;
+pub mod generate;
+pub mod output;
+pub mod parts;
+pub mod reconcile;
+pub mod render;
+pub mod report;
+pub mod repl;
+pub mod snapshot;
+pub mod watch;
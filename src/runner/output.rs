@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+// what a single part printed during one run of the day
+pub struct PartRun {
+    pub part: char,
+    pub output: Vec<String>,
+    pub took: String,
+}
+
+impl PartRun {
+    // solutions print their answer last, so we treat the last non-empty line as the answer
+    pub fn answer(&self) -> &str {
+        self.output
+            .iter()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| line.trim())
+            .unwrap_or("(no output)")
+    }
+
+    // the time the part reported for itself, as printed by Duration's Debug, e.g. 1.5s, 12.3ms or 850ns
+    pub fn duration(&self) -> Option<Duration> {
+        let took = self.took.split_whitespace().next()?;
+        let unit_start = took.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (value, unit) = took.split_at(unit_start);
+        let value: f64 = value.parse().ok()?;
+        let seconds = match unit {
+            "s" => value,
+            "ms" => value / 1e3,
+            "µs" => value / 1e6,
+            "ns" => value / 1e9,
+            _ => return None,
+        };
+        Some(Duration::from_secs_f64(seconds))
+    }
+}
+
+// every part ends with a "run_x took: ..." line, so everything printed before it belongs to that part
+pub fn parse_parts(stdout: &str, stderr: &str, day: u8) -> Vec<PartRun> {
+    let mut parts = Vec::new();
+    let mut output = Vec::new();
+
+    for line in stdout.lines() {
+        let took = line
+            .strip_prefix("run_")
+            .and_then(|rest| rest.split_once(" took: "));
+
+        match took {
+            Some((part, took)) if part.len() == 1 => {
+                let part = part.to_ascii_uppercase().chars().next().unwrap();
+
+                // errors go to stderr, so pull in the ones that belong to this part
+                let error_prefix = format!("Error running day {} part {}: ", day, part);
+                output.extend(
+                    stderr
+                        .lines()
+                        .filter(|line| line.starts_with(&error_prefix))
+                        .map(String::from),
+                );
+
+                parts.push(PartRun {
+                    part,
+                    output: std::mem::take(&mut output),
+                    took: took.to_string(),
+                });
+            }
+            _ => output.push(line.to_string()),
+        }
+    }
+
+    parts
+}
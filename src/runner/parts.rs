@@ -0,0 +1,145 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::cancellation::{self, CancellationToken};
+
+// A single solvable part of a day, e.g. day 7 part B
+pub struct Part {
+    pub day: u8,
+    pub part: char,
+    pub run: fn() -> Result<(), String>,
+}
+
+// wraps a day's run_a / run_b so every part has the same signature, no matter which error type the day returns
+macro_rules! part {
+    ($day:literal, $module:ident, $part:literal, $run:ident) => {
+        Part {
+            day: $day,
+            part: $part,
+            run: || crate::$module::$module::$run().map_err(|e| format!("{:?}", e)),
+        }
+    };
+}
+
+// every part in the order they are run
+pub fn all_parts() -> Vec<Part> {
+    vec![
+        part!(1, day_1, 'A', run_a),
+        part!(1, day_1, 'B', run_b),
+        part!(2, day_2, 'A', run_a),
+        part!(2, day_2, 'B', run_b),
+        part!(3, day_3, 'A', run_a),
+        part!(3, day_3, 'B', run_b),
+        part!(4, day_4, 'A', run_a),
+        part!(4, day_4, 'B', run_b),
+        part!(5, day_5, 'A', run_a),
+        part!(5, day_5, 'B', run_b),
+        part!(6, day_6, 'A', run_a),
+        part!(6, day_6, 'B', run_b),
+        part!(7, day_7, 'A', run_a),
+        part!(7, day_7, 'B', run_b),
+        part!(8, day_8, 'A', run_a),
+        part!(8, day_8, 'B', run_b),
+        part!(9, day_9, 'A', run_a),
+        part!(9, day_9, 'B', run_b),
+        part!(10, day_10, 'A', run_a),
+        part!(10, day_10, 'B', run_b),
+        part!(11, day_11, 'A', run_a),
+        part!(11, day_11, 'B', run_b),
+        part!(12, day_12, 'A', run_a),
+        part!(12, day_12, 'B', run_b),
+        part!(13, day_13, 'A', run_a),
+        part!(13, day_13, 'B', run_b),
+        part!(14, day_14, 'A', run_a),
+        part!(14, day_14, 'B', run_b),
+        part!(15, day_15, 'A', run_a),
+        part!(15, day_15, 'B', run_b),
+        part!(16, day_16, 'A', run_a),
+        part!(16, day_16, 'B', run_b),
+        part!(17, day_17, 'A', run_a),
+        part!(17, day_17, 'B', run_b),
+        part!(18, day_18, 'A', run_a),
+        part!(18, day_18, 'B', run_b),
+        part!(19, day_19, 'A', run_a),
+        part!(19, day_19, 'B', run_b),
+        part!(20, day_20, 'A', run_a),
+        part!(20, day_20, 'B', run_b),
+        part!(21, day_21, 'A', run_a),
+        part!(21, day_21, 'B', run_b),
+        part!(22, day_22, 'A', run_a),
+        part!(22, day_22, 'B', run_b),
+        part!(23, day_23, 'A', run_a),
+        part!(23, day_23, 'B', run_b),
+        part!(24, day_24, 'A', run_a),
+        part!(24, day_24, 'B', run_b),
+        part!(25, day_25, 'A', run_a),
+        part!(25, day_25, 'B', run_b),
+    ]
+}
+
+pub fn parts_for_day(day: u8) -> Vec<Part> {
+    all_parts().into_iter().filter(|part| part.day == day).collect()
+}
+
+pub enum Outcome {
+    Solved,
+    Failed(String),
+    TimedOut,
+}
+
+// runs a single part, printing any error and how long it took, and returns how it went along with that time.
+// with a time budget the part runs on its own thread, and if it goes over we cancel its token and move on without it
+pub fn run_part(part: &Part, timeout: Option<Duration>) -> (Outcome, Duration) {
+    let start = Instant::now();
+    let outcome = match timeout {
+        None => match (part.run)() {
+            Ok(()) => Outcome::Solved,
+            Err(e) => Outcome::Failed(e),
+        },
+        Some(timeout) => run_with_timeout(part, timeout),
+    };
+    let duration = start.elapsed();
+
+    match &outcome {
+        Outcome::Solved => {}
+        Outcome::Failed(e) => eprintln!("Error running day {} part {}: {}", part.day, part.part, e),
+        Outcome::TimedOut => eprintln!("Day {} part {} timed out after {:?}", part.day, part.part, duration),
+    }
+
+    let timed_out = if matches!(outcome, Outcome::TimedOut) { " (timed out)" } else { "" };
+    println!("run_{} took: {:?}{}", part.part.to_ascii_lowercase(), duration, timed_out);
+
+    (outcome, duration)
+}
+
+fn run_with_timeout(part: &Part, timeout: Duration) -> Outcome {
+    let token = CancellationToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let run = part.run;
+    let thread_token = token.clone();
+
+    let spawned = thread::Builder::new()
+        .name(format!("day {} part {}", part.day, part.part))
+        // match the main thread's stack, some of the solutions recurse quite deeply
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            cancellation::set_current(thread_token);
+            // the receiver is gone if we already timed out, so there's no one to tell
+            let _ = sender.send(run());
+        });
+
+    if let Err(e) = spawned {
+        return Outcome::Failed(format!("could not start thread: {}", e));
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(())) => Outcome::Solved,
+        Ok(Err(e)) => Outcome::Failed(e),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Outcome::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => Outcome::Failed("panicked".to_string()),
+    }
+}
@@ -0,0 +1,19 @@
+use std::io;
+use std::path::PathBuf;
+
+use crate::utils::read_as_columns::RaggedRows;
+
+pub struct ReconcileOptions {
+    pub input: PathBuf,
+    // None splits on any whitespace
+    pub delimiter: Option<String>,
+    // take the list names from the first line
+    pub header: bool,
+    // what to do with lines that don't have a value for every list
+    pub ragged: RaggedRows,
+}
+
+// compares every list in the input with every other one, using day 1's distance and similarity
+pub fn reconcile(options: &ReconcileOptions) -> io::Result<String> {
+    crate::day_1::day_1::reconcile(&options.input, options.delimiter.as_deref(), options.header, options.ragged)
+}
@@ -0,0 +1,65 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::render::animation::GifWriter;
+use crate::render::grid_renderer::GridRenderer;
+use crate::render::text::with_caption;
+
+pub struct RenderOptions {
+    pub day: u8,
+    pub output_dir: PathBuf,
+    pub cell_size: u32,
+    // an animated GIF of the simulation instead of a single PNG of the end result
+    pub animate: bool,
+    // a line of text drawn above the grid, e.g. to say what the colours mean
+    pub caption: Option<String>,
+}
+
+// renders the day's grid to `output_dir`, returning the path of the file it wrote
+pub fn render_day(options: &RenderOptions) -> io::Result<PathBuf> {
+    let renderer = GridRenderer::new(options.cell_size);
+
+    // nothing is created on disk until the day is known to be supported, so an unsupported one doesn't leave an
+    // empty file behind
+    if options.animate {
+        let path = options.output_dir.join(format!("day_{}.gif", options.day));
+        match options.day {
+            6 => {
+                fs::create_dir_all(&options.output_dir)?;
+                let mut gif = GifWriter::create(&path, Duration::from_millis(50))?.with_caption(options.caption.clone());
+                crate::day_6::day_6::render_animation(&renderer, &mut gif, 25)?
+            }
+            _ => return Err(unsupported(options.day, "animations")),
+        }
+        return Ok(path);
+    }
+
+    let image = match options.day {
+        6 => crate::day_6::day_6::render(&renderer)?,
+        8 => crate::day_8::day_8::render(&renderer)?,
+        10 => crate::day_10::day_10::render(&renderer)?,
+        12 => crate::day_12::day_12::render(&renderer)?,
+        16 => crate::day_16::day_16::render(&renderer)?,
+        18 => crate::day_18::day_18::render(&renderer)?,
+        _ => return Err(unsupported(options.day, "images")),
+    };
+
+    let image = match &options.caption {
+        Some(caption) => with_caption(&image, caption),
+        None => image,
+    };
+
+    fs::create_dir_all(&options.output_dir)?;
+    let path = options.output_dir.join(format!("day_{}.png", options.day));
+    image.save(&path).map_err(io::Error::other)?;
+    Ok(path)
+}
+
+fn unsupported(day: u8, what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("day {} doesn't have any {} to render", day, what),
+    )
+}
@@ -0,0 +1,32 @@
+use std::io;
+
+use crate::utils::repl::{run_repl, ReplSession};
+
+pub struct ReplOptions {
+    pub day: u8,
+    pub input: Option<String>,
+}
+
+// loads the day's puzzle state and hands it to the repl loop
+pub fn repl_day(options: &ReplOptions) -> io::Result<()> {
+    if let Some(input) = &options.input {
+        crate::utils::input::set_input_path(input.clone()).map_err(io::Error::other)?;
+    }
+
+    let mut session: Box<dyn ReplSession> = match options.day {
+        15 => Box::new(crate::day_15::day_15::WarehouseSession::load()?),
+        17 => Box::new(
+            crate::day_17::day_17::DeviceSession::load()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
+        ),
+        23 => Box::new(crate::day_23::day_23::NetworkSession::load()?),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("day {} doesn't have a repl", options.day),
+            ))
+        }
+    };
+
+    run_repl(&format!("day {}", options.day), session.as_mut())
+}
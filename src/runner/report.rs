@@ -0,0 +1,26 @@
+use std::io::{self, Write};
+
+use crate::utils::report::ReportFormat;
+
+pub struct ReportOptions {
+    pub day: u8,
+    pub input: Option<String>,
+    pub format: ReportFormat,
+}
+
+// explains the day's answer item by item, writing it to out
+pub fn report_day(options: &ReportOptions, out: &mut dyn Write) -> io::Result<()> {
+    if let Some(input) = &options.input {
+        crate::utils::input::set_input_path(input.clone()).map_err(io::Error::other)?;
+    }
+
+    match options.day {
+        2 => out.write_all(crate::day_2::day_2::report(options.format)?.as_bytes()),
+        3 => crate::day_3::day_3::report(options.format, out),
+        5 => out.write_all(crate::day_5::day_5::report(options.format)?.as_bytes()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("day {} doesn't have a report", options.day),
+        )),
+    }
+}
@@ -0,0 +1,117 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::utils::snapshot::{check_snapshot, SnapshotOutcome};
+
+pub struct SnapshotOptions {
+    pub day: u8,
+    // how many moves / seconds to simulate before taking the snapshot
+    pub steps: usize,
+    // defaults to the day's input_example.txt, real inputs aren't checked in
+    pub input: Option<PathBuf>,
+    pub accept: bool,
+}
+
+type Snapshots = fn(&Path, usize) -> io::Result<Vec<(&'static str, String)>>;
+
+// checks the day's grids against their golden files in src/day_N/snapshots, returning whether they all matched.
+// Accepted and newly created snapshots count as matching, since the golden files now agree with the code
+pub fn snapshot_day(options: &SnapshotOptions) -> io::Result<bool> {
+    let mut all_matched = true;
+
+    for (golden, label, actual) in take_snapshots(options)? {
+        match check_snapshot(&golden, &actual, options.accept)? {
+            SnapshotOutcome::Matched => println!("{}: matches {}", label, golden.display()),
+            SnapshotOutcome::Created => println!("{}: created {}", label, golden.display()),
+            SnapshotOutcome::Accepted { diff } => {
+                println!("{}: accepted changes to {}\n{}", label, golden.display(), diff);
+            }
+            SnapshotOutcome::Mismatch { diff, pending } => {
+                all_matched = false;
+                println!(
+                    "{}: differs from {}\n{}new snapshot written to {}, re-run with --accept to keep it",
+                    label,
+                    golden.display(),
+                    diff,
+                    pending.display()
+                );
+            }
+        }
+    }
+
+    Ok(all_matched)
+}
+
+// each of the day's grids with the golden file it should match and a label for it
+fn take_snapshots(options: &SnapshotOptions) -> io::Result<Vec<(PathBuf, String, String)>> {
+    let snapshots: Snapshots = match options.day {
+        14 => |input, steps| crate::day_14::day_14::snapshots(input, steps),
+        15 => |input, steps| crate::day_15::day_15::snapshots(input, steps),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("day {} doesn't have any grids to snapshot", options.day),
+            ))
+        }
+    };
+
+    let day_dir = PathBuf::from(format!("src/day_{}", options.day));
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| day_dir.join("input_example.txt"));
+    let input_name = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "input".to_string());
+
+    Ok(snapshots(&input, options.steps)?
+        .into_iter()
+        .map(|(name, actual)| {
+            let golden = day_dir
+                .join("snapshots")
+                .join(format!("{}_{}_{}.txt", name, input_name, options.steps));
+            let label = format!("Day {} {} after {} steps", options.day, name, options.steps);
+            (golden, label, actual)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::snapshot::compare_snapshot;
+
+    // against the golden files checked in under src/day_N/snapshots, for the day's input_example.txt. nothing is
+    // written, a missing golden file fails rather than being created
+    fn assert_matches_golden(day: u8, steps: usize) {
+        let options = SnapshotOptions {
+            day,
+            steps,
+            input: None,
+            accept: false,
+        };
+        for (golden, label, actual) in take_snapshots(&options).unwrap() {
+            match compare_snapshot(&golden, &actual) {
+                Ok(None) => {}
+                Ok(Some(diff)) => panic!("{} differs from {}\n{}", label, golden.display(), diff),
+                Err(e) => panic!("{}: can't read {}: {}", label, golden.display(), e),
+            }
+        }
+    }
+
+    #[test]
+    fn day_14_after_100_seconds() {
+        assert_matches_golden(14, 100);
+    }
+
+    #[test]
+    fn day_15_after_10_moves() {
+        assert_matches_golden(15, 10);
+    }
+
+    #[test]
+    fn day_15_after_every_move() {
+        assert_matches_golden(15, 10_000);
+    }
+}
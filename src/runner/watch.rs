@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::runner::output::{parse_parts, PartRun};
+
+pub struct WatchOptions {
+    pub day: u8,
+    pub watch_source: bool,
+    pub interval: Duration,
+    pub timeout: Option<Duration>,
+}
+
+pub fn watch(options: &WatchOptions) -> io::Result<()> {
+    let day_dir = PathBuf::from(format!("src/day_{}", options.day));
+    if !day_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist, run watch from the repository root", day_dir.display()),
+        ));
+    }
+
+    let mut modified_times = get_modified_times(&day_dir, options.watch_source)?;
+    let mut previous_run: Option<Vec<PartRun>> = None;
+    let mut run_count = 1;
+
+    println!("Watching {} for changes (Ctrl+C to stop)", day_dir.display());
+
+    loop {
+        match run_day(options) {
+            Ok(parts) => {
+                print_comparison(options.day, run_count, &parts, previous_run.as_deref());
+                previous_run = Some(parts);
+            }
+            Err(e) => eprintln!("Error running day {}: {}", options.day, e),
+        }
+        run_count += 1;
+
+        // poll until something we're watching has been added, removed or modified
+        loop {
+            thread::sleep(options.interval);
+            // a failed scan is just tried again next time round rather than stopping the watcher
+            let latest_times = match get_modified_times(&day_dir, options.watch_source) {
+                Ok(latest_times) => latest_times,
+                Err(e) => {
+                    eprintln!("Error checking {} for changes: {}", day_dir.display(), e);
+                    continue;
+                }
+            };
+            if latest_times != modified_times {
+                for path in changed_paths(&modified_times, &latest_times) {
+                    println!("\nChanged: {}", path.display());
+                }
+                modified_times = latest_times;
+                break;
+            }
+        }
+    }
+}
+
+// input files are everything in the day's folder that isn't source, source files are only included when asked for
+fn get_modified_times(day_dir: &Path, watch_source: bool) -> io::Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut modified_times = BTreeMap::new();
+
+    for entry in fs::read_dir(day_dir)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let is_source = path.extension().is_some_and(|ext| ext == "rs");
+        if is_source && !watch_source {
+            continue;
+        }
+
+        // an editor's atomic save can rename the temp file away between listing it and reading its metadata
+        match entry.metadata().and_then(|metadata| metadata.modified()) {
+            Ok(modified) => {
+                modified_times.insert(path, modified);
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(modified_times)
+}
+
+fn changed_paths<'a>(
+    before: &'a BTreeMap<PathBuf, SystemTime>,
+    after: &'a BTreeMap<PathBuf, SystemTime>,
+) -> Vec<&'a PathBuf> {
+    let mut changed: Vec<&PathBuf> = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(*time))
+        .map(|(path, _)| path)
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)));
+    changed
+}
+
+// runs the day in a child process so a change to the source can be rebuilt by cargo and a panic doesn't stop the watcher
+fn run_day(options: &WatchOptions) -> io::Result<Vec<PartRun>> {
+    let mut command = if options.watch_source {
+        let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let mut command = Command::new(cargo);
+        command.args(["run", "--quiet"]);
+        if !cfg!(debug_assertions) {
+            command.arg("--release");
+        }
+        command.arg("--");
+        command
+    } else {
+        Command::new(env::current_exe()?)
+    };
+
+    command.args(["--day", &options.day.to_string()]);
+    if let Some(timeout) = options.timeout {
+        command.args(["--timeout", &timeout.as_millis().to_string()]);
+    }
+
+    let output = command.output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let parts = parse_parts(&stdout, &stderr, options.day);
+
+    if parts.is_empty() {
+        return Err(io::Error::other(format!(
+            "no parts finished ({})\n{}",
+            output.status,
+            stderr.trim_end()
+        )));
+    }
+
+    Ok(parts)
+}
+
+fn print_comparison(day: u8, run_count: usize, parts: &[PartRun], previous_run: Option<&[PartRun]>) {
+    println!("\n=== Day {} (run #{}) ===", day, run_count);
+
+    for part in parts {
+        println!("Part {}: {}  [{}]", part.part, part.answer(), part.took);
+
+        let previous = previous_run.and_then(|previous| previous.iter().find(|p| p.part == part.part));
+        if let Some(previous) = previous {
+            let marker = if previous.answer() == part.answer() { "unchanged" } else { "CHANGED" };
+            println!("  was: {}  [{}]  ({})", previous.answer(), previous.took, marker);
+        }
+    }
+}
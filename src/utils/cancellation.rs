@@ -0,0 +1,47 @@
+use std::cell::RefCell;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// A flag the runner flips when a part has gone over its time budget.
+// Solvers can't be stopped from the outside, so long running loops should check it and bail out.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // convenience for solvers returning io::Result, so a hot loop only needs `token.check()?`
+    pub fn check(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            Err(io::Error::new(io::ErrorKind::TimedOut, "cancelled after running over its time budget"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<CancellationToken> = RefCell::new(CancellationToken::new());
+}
+
+// the token for the part running on this thread, clone it into any threads the solver spawns itself
+pub fn current() -> CancellationToken {
+    CURRENT.with(|token| token.borrow().clone())
+}
+
+pub fn set_current(token: CancellationToken) {
+    CURRENT.with(|current| *current.borrow_mut() = token);
+}
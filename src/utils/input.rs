@@ -0,0 +1,16 @@
+use std::sync::OnceLock;
+
+static INPUT_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+// Lets a run point every day at another input file, e.g. batch mode solving someone else's input.
+// Can only be set once, before any part runs
+pub fn set_input_path(path: String) -> Result<(), String> {
+    INPUT_OVERRIDE
+        .set(path)
+        .map_err(|path| format!("Input path already set, can't change it to {}", path))
+}
+
+// the overridden input if there is one, otherwise the day's own input file
+pub fn input_path(default: &'static str) -> &'static str {
+    INPUT_OVERRIDE.get().map(String::as_str).unwrap_or(default)
+}
@@ -1,8 +1,13 @@
 pub mod read_lines;
 pub mod read_as_columns;
 pub mod transpose_into_columns;
-pub mod read_as_rows;
 pub mod read_as_string;
 pub mod read_lines_as_int_arrays;
 pub mod read_as_matrix;
-pub mod read_as_map;
\ No newline at end of file
+pub mod cancellation;
+pub mod snapshot;
+pub mod input;
+pub mod repl;
+pub mod stream_lines;
+pub mod sparse_grid;
+pub mod report;
\ No newline at end of file
@@ -1,30 +1,157 @@
-use std::io::{self};
+use std::io::{self, BufRead};
 use std::path::Path;
-use crate::utils::read_lines::read_lines;
+use std::str::FromStr;
+use crate::utils::stream_lines::open_buffered;
 
-pub fn read_as_columns<P>(filename: P) -> io::Result<Vec<Vec<String>>>
+// what to do with a row that doesn't have the same number of fields as the others
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    // fail, naming the first row that doesn't match the first one
+    #[default]
+    Error,
+    // fill short rows with the column type's default value up to the widest row
+    Pad,
+    // only keep as many columns as the first row has, dropping any fields past them. shorter rows are padded
+    Truncate,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ColumnOptions<'a> {
+    // None splits on any whitespace
+    pub delimiter: Option<&'a str>,
+    // how many lines to skip before the values start
+    pub skip_header: usize,
+    pub ragged: RaggedRows,
+}
+
+pub fn read_as_typed_columns<P, T>(filename: P, options: &ColumnOptions) -> io::Result<Vec<Vec<T>>>
 where
     P: AsRef<Path>,
+    T: FromStr + Default + Clone,
+{
+    columns_from_reader(open_buffered(filename)?, options)
+}
+
+fn columns_from_reader<R, T>(reader: R, options: &ColumnOptions) -> io::Result<Vec<Vec<T>>>
+where
+    R: BufRead,
+    T: FromStr + Default + Clone,
 {
-    let lines = read_lines(filename)?;
-    let mut columns: Vec<Vec<String>> = Vec::new();
-    
-    // Process each line
-    for line in lines {
-        let values: Vec<String> = line.split_whitespace().map(String::from).collect();
-        
-        // Initialize columns if this is the first line
-        if columns.is_empty() {
-            columns = vec![Vec::new(); values.len()];
+    // rows are added to the columns as they're read rather than kept around, so huge inputs only cost their values
+    let mut columns: Vec<Vec<T>> = Vec::new();
+    let mut row_count = 0;
+    let mut first_width = None;
+
+    for (i, line) in reader.lines().enumerate().skip(options.skip_header) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
         }
-        
-        // Add each value to its respective column
-        for (i, value) in values.into_iter().enumerate() {
-            if i < columns.len() {
-                columns[i].push(value);
+
+        let fields: Box<dyn Iterator<Item = &str>> = match options.delimiter {
+            Some(delimiter) => Box::new(line.split(delimiter).map(|field| field.trim())),
+            None => Box::new(line.split_whitespace()),
+        };
+
+        let fields: Box<dyn Iterator<Item = &str>> = match (options.ragged, first_width) {
+            (RaggedRows::Truncate, Some(first_width)) => Box::new(fields.take(first_width)),
+            _ => fields,
+        };
+
+        let mut width = 0;
+        for (index, field) in fields.enumerate() {
+            let value = field.parse::<T>().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: could not parse {:?}", i + 1, field),
+                )
+            })?;
+            if index == columns.len() {
+                // a new widest row, the rows before it are padded
+                columns.push(vec![T::default(); row_count]);
             }
+            columns[index].push(value);
+            width = index + 1;
         }
+
+        let expected = *first_width.get_or_insert(width);
+        if options.ragged == RaggedRows::Error && width != expected {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: expected {} fields, found {}", i + 1, expected, width),
+            ));
+        }
+
+        for column in &mut columns[width..] {
+            column.push(T::default());
+        }
+        row_count += 1;
     }
-    
+
     Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(input: &str, options: &ColumnOptions) -> io::Result<Vec<Vec<i64>>> {
+        columns_from_reader(input.as_bytes(), options)
+    }
+
+    fn with_ragged(ragged: RaggedRows) -> ColumnOptions<'static> {
+        ColumnOptions {
+            ragged,
+            ..ColumnOptions::default()
+        }
+    }
+
+    #[test]
+    fn splits_rows_into_columns() {
+        let options = ColumnOptions::default();
+        assert_eq!(columns("3   4\n4   3\n\n2   5\n", &options).unwrap(), vec![vec![3, 4, 2], vec![4, 3, 5]]);
+    }
+
+    #[test]
+    fn splits_on_the_delimiter() {
+        let options = ColumnOptions {
+            delimiter: Some(","),
+            ..ColumnOptions::default()
+        };
+        assert_eq!(columns("1, 2\n3 ,4\n", &options).unwrap(), vec![vec![1, 3], vec![2, 4]]);
+    }
+
+    #[test]
+    fn skips_the_header() {
+        let options = ColumnOptions {
+            skip_header: 1,
+            ..ColumnOptions::default()
+        };
+        assert_eq!(columns("left right\n1 2\n3 4\n", &options).unwrap(), vec![vec![1, 3], vec![2, 4]]);
+
+        // without skipping it the names don't parse
+        let error = columns("left right\n1 2\n", &ColumnOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("line 1"), "{}", error);
+    }
+
+    #[test]
+    fn ragged_rows_are_an_error_by_default() {
+        let error = columns("1 2\n3\n", &ColumnOptions::default()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 2 fields, found 1");
+
+        let error = columns("1 2\n3 4 5\n", &with_ragged(RaggedRows::Error)).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 2 fields, found 3");
+    }
+
+    #[test]
+    fn pad_fills_every_row_up_to_the_widest() {
+        let columns = columns("1 2\n3\n4 5 6\n", &with_ragged(RaggedRows::Pad)).unwrap();
+        assert_eq!(columns, vec![vec![1, 3, 4], vec![2, 0, 5], vec![0, 0, 6]]);
+    }
+
+    #[test]
+    fn truncate_keeps_the_first_rows_columns() {
+        let columns = columns("1 2\n3\n4 5 6\n", &with_ragged(RaggedRows::Truncate)).unwrap();
+        assert_eq!(columns, vec![vec![1, 3, 4], vec![2, 0, 5]]);
+    }
 }
\ No newline at end of file
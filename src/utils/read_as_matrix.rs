@@ -1,9 +1,10 @@
-use std::fs;
+use std::io;
+
+use crate::utils::stream_lines::{open_buffered, stream_grid_rows};
 
 pub fn read_as_matrix(filename: &str) -> Vec<Vec<char>> {
-    fs::read_to_string(filename)
+    let reader = open_buffered(filename).expect("Failed to read input file");
+    stream_grid_rows(reader)
+        .collect::<io::Result<_>>()
         .expect("Failed to read input file")
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect()
 }
\ No newline at end of file
@@ -1,17 +1,141 @@
+use std::fs;
 use std::io::{self};
 use std::path::Path;
-use crate::utils::read_lines::read_lines;
+
+// how the lines of the file are put back together
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    // keep a newline between lines (and at the end if the file had one), so nothing can match across them
+    PreserveNewlines,
+    // an empty separator concatenates them, for inputs that are really one long line wrapped over several
+    Separator(String),
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Separator(String::new())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReadOptions {
+    pub join: LineJoin,
+    // strip trailing whitespace from every line before joining
+    pub trim_trailing: bool,
+}
+
+// the line endings found in the file, which are always normalised away in the text we return
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    // a single line without a newline at the end
+    None,
+    Lf,
+    Crlf,
+    Mixed,
+}
+
+impl LineEnding {
+    pub fn from_counts(lf_count: usize, crlf_count: usize) -> Self {
+        match (lf_count, crlf_count) {
+            (0, 0) => LineEnding::None,
+            (_, 0) => LineEnding::Lf,
+            (0, _) => LineEnding::Crlf,
+            _ => LineEnding::Mixed,
+        }
+    }
+}
+
+pub struct TextInput {
+    pub text: String,
+    pub line_ending: LineEnding,
+}
 
 pub fn read_as_string<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
 {
-    let lines = read_lines(filename)?;
-    
-    let mut full_string = String::new();
-    for line in lines {
-        full_string.push_str(&line);
+    Ok(read_as_string_with(filename, &ReadOptions::default())?.text)
+}
+
+pub fn read_as_string_with<P>(filename: P, options: &ReadOptions) -> io::Result<TextInput>
+where
+    P: AsRef<Path>,
+{
+    Ok(join_lines(&fs::read_to_string(filename)?, options))
+}
+
+fn join_lines(contents: &str, options: &ReadOptions) -> TextInput {
+    let mut lines = Vec::new();
+    let mut lf_count = 0;
+    let mut crlf_count = 0;
+    for line in contents.split_inclusive('\n') {
+        let line = if let Some(line) = line.strip_suffix("\r\n") {
+            crlf_count += 1;
+            line
+        } else if let Some(line) = line.strip_suffix('\n') {
+            lf_count += 1;
+            line
+        } else {
+            line
+        };
+
+        lines.push(if options.trim_trailing { line.trim_end() } else { line });
+    }
+
+    let line_ending = LineEnding::from_counts(lf_count, crlf_count);
+
+    let text = match &options.join {
+        LineJoin::PreserveNewlines => {
+            let mut text = lines.join("\n");
+            if contents.ends_with('\n') {
+                text.push('\n');
+            }
+            text
+        }
+        LineJoin::Separator(separator) => lines.join(separator),
+    };
+
+    TextInput { text, line_ending }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preserving() -> ReadOptions {
+        ReadOptions {
+            join: LineJoin::PreserveNewlines,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preserves_newlines_as_lf() {
+        let input = join_lines("mul(1,2)\r\n\r\nmul(3,4)\r\n", &preserving());
+        assert_eq!(input.text, "mul(1,2)\n\nmul(3,4)\n");
+        assert_eq!(input.line_ending, LineEnding::Crlf);
+
+        // no newline at the end isn't given one
+        assert_eq!(join_lines("a\nb", &preserving()).text, "a\nb");
+    }
+
+    #[test]
+    fn reports_line_endings() {
+        let line_ending = |contents| join_lines(contents, &ReadOptions::default()).line_ending;
+        assert_eq!(line_ending("abc"), LineEnding::None);
+        assert_eq!(line_ending("a\nb\n"), LineEnding::Lf);
+        assert_eq!(line_ending("a\r\nb\r\n"), LineEnding::Crlf);
+        assert_eq!(line_ending("a\r\nb\n"), LineEnding::Mixed);
+    }
+
+    #[test]
+    fn joins_with_a_separator() {
+        assert_eq!(join_lines("ab\r\ncd\n", &ReadOptions::default()).text, "abcd");
+
+        let options = ReadOptions {
+            join: LineJoin::Separator(" ".to_string()),
+            trim_trailing: true,
+        };
+        assert_eq!(join_lines("ab  \ncd\t\n", &options).text, "ab cd");
     }
-    
-    Ok(full_string)
-}
\ No newline at end of file
+}
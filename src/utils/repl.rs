@@ -0,0 +1,62 @@
+use std::io::{self, BufRead, Write};
+
+// A day's parsed puzzle state that can be poked at from the repl command.
+// `help` and `quit` are handled by the loop, everything else is passed to `execute`
+pub trait ReplSession {
+    // (usage, description) for every command, listed by `help`
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    // runs a single command, returning what to print or an error message
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+pub fn run_repl(name: &str, session: &mut dyn ReplSession) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    println!("{} loaded, type help for a list of commands", name);
+
+    loop {
+        print!("{}> ", name);
+        stdout.flush()?;
+
+        let mut line = String::new();
+        // end of input, e.g. Ctrl+D
+        if stdin.lock().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            continue;
+        };
+
+        match command {
+            "quit" | "exit" => return Ok(()),
+            "help" => print_help(session),
+            _ => match session.execute(command, args) {
+                Ok(output) => println!("{}", output.trim_end()),
+                Err(e) => println!("error: {}", e),
+            },
+        }
+    }
+}
+
+fn print_help(session: &dyn ReplSession) {
+    let builtins = [("help", "list the commands"), ("quit", "leave the repl")];
+    let commands: Vec<&(&str, &str)> = session.commands().iter().chain(builtins.iter()).collect();
+    let width = commands.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+
+    for (usage, description) in commands {
+        println!("  {:<width$}  {}", usage, description, width = width);
+    }
+}
+
+// the optional count argument most stepping commands take
+pub fn parse_count(args: &[&str]) -> Result<usize, String> {
+    match args.first() {
+        None => Ok(1),
+        Some(arg) => arg.parse().map_err(|_| format!("expected a number, got {}", arg)),
+    }
+}
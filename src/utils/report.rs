@@ -0,0 +1,35 @@
+// How a day's per-item report is printed, for days that can explain their answer line by line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    // one JSON object per item, in an array
+    Json,
+}
+
+// lines up the cells of every row under the first, which is the header
+pub fn format_table(rows: &[Vec<String>]) -> String {
+    let mut widths = vec![0; rows.iter().map(|row| row.len()).max().unwrap_or(0)];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join(" | ").trim_end());
+        table.push('\n');
+
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            table.push_str(&rule.join("-+-"));
+            table.push('\n');
+        }
+    }
+    table
+}
\ No newline at end of file
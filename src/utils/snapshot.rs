@@ -0,0 +1,111 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub enum SnapshotOutcome {
+    Matched,
+    // there was no golden file yet, so the actual snapshot became the golden one
+    Created,
+    // the golden file differed and was replaced because we were asked to accept changes
+    Accepted { diff: String },
+    // the actual snapshot was written next to the golden file with a `.new` extension
+    Mismatch { diff: String, pending: PathBuf },
+}
+
+// Compares a rendered grid against the golden file at `golden`.
+// A mismatch leaves the new snapshot in `<golden>.new` so it can be looked at, and
+// running again with `accept` set replaces the golden file with it
+pub fn check_snapshot<P>(golden: P, actual: &str, accept: bool) -> io::Result<SnapshotOutcome>
+where
+    P: AsRef<Path>,
+{
+    let golden = golden.as_ref();
+    let pending = pending_path(golden);
+
+    let diff = match compare_snapshot(golden, actual) {
+        Ok(None) => {
+            remove_if_exists(&pending)?;
+            return Ok(SnapshotOutcome::Matched);
+        }
+        Ok(Some(diff)) => diff,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if let Some(dir) = golden.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(golden, actual)?;
+            return Ok(SnapshotOutcome::Created);
+        }
+        Err(e) => return Err(e),
+    };
+
+    if accept {
+        fs::write(golden, actual)?;
+        remove_if_exists(&pending)?;
+        Ok(SnapshotOutcome::Accepted { diff })
+    } else {
+        fs::write(&pending, actual)?;
+        Ok(SnapshotOutcome::Mismatch { diff, pending })
+    }
+}
+
+// The read-only half of check_snapshot: the diff against the golden file, or None when they match.
+// A missing golden file is a NotFound error rather than being created
+pub fn compare_snapshot<P>(golden: P, actual: &str) -> io::Result<Option<String>>
+where
+    P: AsRef<Path>,
+{
+    let expected = fs::read_to_string(golden)?;
+
+    // golden files may have been checked out with CRLF line endings
+    if expected.replace("\r\n", "\n") == actual {
+        Ok(None)
+    } else {
+        Ok(Some(line_diff(&expected, actual)))
+    }
+}
+
+// Lists every line that differs, with the expected line first and a row of carets under the changed columns.
+// Grids keep their size from one step to the next, so comparing line by line reads better than a minimal diff would
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut output = String::new();
+
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        match (expected_lines.get(i), actual_lines.get(i)) {
+            (Some(e), Some(a)) if e == a => {}
+            (Some(e), Some(a)) => {
+                let e_chars: Vec<char> = e.chars().collect();
+                let a_chars: Vec<char> = a.chars().collect();
+                let markers: String = (0..e_chars.len().max(a_chars.len()))
+                    .map(|j| if e_chars.get(j) == a_chars.get(j) { ' ' } else { '^' })
+                    .collect();
+
+                output.push_str(&format!("line {}:\n- {}\n+ {}\n  {}\n", i + 1, e, a, markers.trim_end()));
+            }
+            (Some(e), None) => output.push_str(&format!("line {} missing:\n- {}\n", i + 1, e)),
+            (None, Some(a)) => output.push_str(&format!("line {} added:\n+ {}\n", i + 1, a)),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    if output.is_empty() {
+        // same lines, so the only difference is in the line endings
+        output.push_str("only the trailing newlines differ\n");
+    }
+
+    output
+}
+
+fn pending_path(golden: &Path) -> PathBuf {
+    let mut pending = golden.as_os_str().to_owned();
+    pending.push(".new");
+    PathBuf::from(pending)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// A grid that only stores the cells that hold something, addressed by (x, y) with no fixed origin,
+// so it can grow in any direction or start somewhere other than (0, 0)
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    fn at(x: i64, y: i64) -> Self {
+        Bounds { min_x: x, max_x: x, min_y: y, max_y: y }
+    }
+
+    fn extend(&mut self, x: i64, y: i64) {
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
+        self.min_y = self.min_y.min(y);
+        self.max_y = self.max_y.max(y);
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseGridOptions {
+    // the coordinates of the first character of the first line
+    pub origin: (i64, i64),
+    // characters that mark an empty cell, they aren't stored but still count towards the bounds
+    pub background: Vec<char>,
+    // treat any character parse_cell doesn't accept as background, rather than as an error
+    pub skip_unknown: bool,
+}
+
+impl Default for SparseGridOptions {
+    fn default() -> Self {
+        SparseGridOptions {
+            origin: (0, 0),
+            background: vec!['.'],
+            skip_unknown: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(i64, i64), T>,
+    // kept up to date on every insert, so it never needs a scan over the cells
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    // parse_cell turns every character that isn't background into a cell, or None if it's not valid there, which
+    // is an error unless skip_unknown is set
+    pub fn parse<F>(input: &str, options: &SparseGridOptions, mut parse_cell: F) -> Result<Self, String>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut grid = SparseGrid::new();
+        let (origin_x, origin_y) = options.origin;
+
+        for (row, line) in input.lines().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                let (x, y) = (origin_x + column as i64, origin_y + row as i64);
+                if options.background.contains(&c) {
+                    grid.extend_bounds(x, y);
+                    continue;
+                }
+
+                match parse_cell(c) {
+                    Some(cell) => {
+                        grid.insert(x, y, cell);
+                    }
+                    None if options.skip_unknown => grid.extend_bounds(x, y),
+                    None => return Err(format!("line {} column {}: unexpected {:?}", row + 1, column + 1, c)),
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+
+    pub fn insert(&mut self, x: i64, y: i64, cell: T) -> Option<T> {
+        self.extend_bounds(x, y);
+        self.cells.insert((x, y), cell)
+    }
+
+    // the smallest box around everything inserted or read, None for an empty grid
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.cells.iter().map(|(&position, cell)| (position, cell))
+    }
+
+    fn extend_bounds(&mut self, x: i64, y: i64) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(x, y),
+            None => self.bounds = Some(Bounds::at(x, y)),
+        }
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+pub fn read_as_sparse_grid<P, T, F>(filename: P, options: &SparseGridOptions, parse_cell: F) -> io::Result<SparseGrid<T>>
+where
+    P: AsRef<Path>,
+    F: FnMut(char) -> Option<T>,
+{
+    let contents = fs::read_to_string(&filename)?;
+    SparseGrid::parse(&contents, options, parse_cell).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", filename.as_ref().display(), e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(input: &str, options: &SparseGridOptions) -> Result<SparseGrid<char>, String> {
+        SparseGrid::parse(input, options, |c| c.is_ascii_alphabetic().then_some(c))
+    }
+
+    fn cells(grid: &SparseGrid<char>) -> Vec<((i64, i64), char)> {
+        let mut cells: Vec<((i64, i64), char)> = grid.iter().map(|(position, &c)| (position, c)).collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn starts_at_the_origin() {
+        let options = SparseGridOptions {
+            origin: (-3, 5),
+            ..Default::default()
+        };
+        let grid = letters("a.\n.b", &options).unwrap();
+
+        assert_eq!(cells(&grid), vec![((-3, 5), 'a'), ((-2, 6), 'b')]);
+        assert_eq!(grid.bounds(), Some(Bounds { min_x: -3, max_x: -2, min_y: 5, max_y: 6 }));
+        assert!(grid.bounds().unwrap().contains(-3, 6));
+        assert!(!grid.bounds().unwrap().contains(-4, 5));
+    }
+
+    #[test]
+    fn background_extends_the_bounds_without_being_stored() {
+        let grid = letters("....\n.a..\n....", &SparseGridOptions::default()).unwrap();
+
+        assert_eq!(cells(&grid), vec![((1, 1), 'a')]);
+        assert_eq!(grid.bounds(), Some(Bounds { min_x: 0, max_x: 3, min_y: 0, max_y: 2 }));
+
+        // all background still has bounds, just no cells
+        let grid = letters("..\n..", &SparseGridOptions::default()).unwrap();
+        assert!(cells(&grid).is_empty());
+        assert_eq!(grid.bounds(), Some(Bounds { min_x: 0, max_x: 1, min_y: 0, max_y: 1 }));
+    }
+
+    #[test]
+    fn unexpected_character_is_an_error_at_its_position() {
+        let error = letters("..\n.a#", &SparseGridOptions::default()).err().unwrap();
+        assert_eq!(error, "line 2 column 3: unexpected '#'");
+    }
+
+    #[test]
+    fn unknown_characters_can_be_skipped() {
+        let options = SparseGridOptions {
+            skip_unknown: true,
+            ..Default::default()
+        };
+        let grid = letters("#a\n..#", &options).unwrap();
+
+        assert_eq!(cells(&grid), vec![((1, 0), 'a')]);
+        assert_eq!(grid.bounds(), Some(Bounds { min_x: 0, max_x: 2, min_y: 0, max_y: 1 }));
+    }
+
+    #[test]
+    fn empty_input_has_no_bounds() {
+        assert_eq!(letters("", &SparseGridOptions::default()).unwrap().bounds(), None);
+        assert_eq!(letters("\n\n", &SparseGridOptions::default()).unwrap().bounds(), None);
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+}
@@ -0,0 +1,86 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+// Readers that parse one line at a time as they're iterated, instead of loading the whole file first,
+// so a solution that doesn't need to keep every line around runs in constant memory
+
+pub fn open_buffered<P>(filename: P) -> io::Result<BufReader<File>>
+where
+    P: AsRef<Path>,
+{
+    Ok(BufReader::new(File::open(filename)?))
+}
+
+pub fn stream_lines<R>(reader: R) -> impl Iterator<Item = io::Result<String>>
+where
+    R: BufRead,
+{
+    reader.lines()
+}
+
+// every non-empty line as a row of numbers split by `sep`, or by any whitespace when it's None
+pub fn stream_int_rows<'a, R, T>(reader: R, sep: Option<&'a str>) -> impl Iterator<Item = io::Result<Vec<T>>> + 'a
+where
+    R: BufRead + 'a,
+    T: FromStr + 'a,
+{
+    reader.lines().enumerate().filter_map(move |(i, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e)),
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        let row = match sep {
+            Some(sep) => line.split(sep).map(|value| parse_number(value, i + 1)).collect(),
+            None => line.split_whitespace().map(|value| parse_number(value, i + 1)).collect(),
+        };
+        Some(row)
+    })
+}
+
+// every number in the input in order, however they're spread over the lines
+pub fn stream_numbers<'a, R, T>(reader: R) -> impl Iterator<Item = io::Result<T>> + 'a
+where
+    R: BufRead + 'a,
+    T: FromStr + 'a,
+{
+    stream_int_rows(reader, None).flat_map(|row| match row {
+        Ok(values) => values.into_iter().map(Ok).collect::<Vec<_>>(),
+        Err(e) => vec![Err(e)],
+    })
+}
+
+// every line of the input as a row of characters
+pub fn stream_grid_rows<R>(reader: R) -> impl Iterator<Item = io::Result<Vec<char>>>
+where
+    R: BufRead,
+{
+    reader.lines().map(|line| line.map(|line| line.chars().collect()))
+}
+
+// the rows of a grid that's followed by something else, e.g. day 15's moves. blank lines before the grid are
+// skipped and it ends at the next one, which is used up so the reader carries on with whatever comes after
+pub fn stream_grid_section<R>(reader: &mut R) -> impl Iterator<Item = io::Result<Vec<char>>> + '_
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .skip_while(|line| line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .take_while(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+        .map(|line| line.map(|line| line.chars().collect()))
+}
+
+fn parse_number<T: FromStr>(value: &str, line_number: usize) -> io::Result<T> {
+    value.trim().parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {}: {:?} is not a number", line_number, value),
+        )
+    })
+}
@@ -1,40 +1,136 @@
-pub fn transpose_into_columns(lines: Vec<String>) -> Vec<Vec<char>> {
-    if lines.is_empty() {
-        return vec![];
-    }
-
-    // Find the maximum line length
-    let max_length = lines.iter().map(|line| line.len()).max().unwrap_or(0);
-    let mut columns: Vec<Vec<char>> = vec![Vec::new(); max_length];
-
-    // Process each line
-    for line in lines {
-        // Pad shorter lines with spaces to maintain column alignment
-        let padded: String = if line.len() < max_length {
-            line.clone() + &" ".repeat(max_length - line.len())
-        } else {
-            line
-        };
+#[derive(Clone, Copy, Debug)]
+pub struct TransposeOptions {
+    // stands in for the cells of rows shorter than the longest one
+    pub fill: char,
+    // drop whitespace cells from each column, the column itself is kept even if that leaves it empty
+    pub skip_whitespace: bool,
+}
 
-        // Add each character to its respective column
-        for (col_idx, ch) in padded.chars().enumerate() {
-            columns[col_idx].push(ch);
+impl Default for TransposeOptions {
+    fn default() -> Self {
+        TransposeOptions {
+            fill: ' ',
+            skip_whitespace: false,
         }
     }
+}
 
-    // Clean up each column: trim whitespace and filter out empty columns
-    columns
-        .into_iter()
-        .filter_map(|col| {
-            let cleaned: Vec<char> = col
-                .into_iter()
-                .filter(|&c| !c.is_whitespace())
-                .collect();
-            if cleaned.is_empty() {
-                None
-            } else {
-                Some(cleaned)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Column {
+    // the position of the column in the original rows
+    pub index: usize,
+    pub cells: Vec<char>,
+}
+
+// anything that can be read as one row of characters, so a grid and a list of lines transpose the same way
+pub trait CharRow {
+    fn cells(&self) -> impl Iterator<Item = char> + '_;
+}
+
+impl CharRow for Vec<char> {
+    fn cells(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl CharRow for String {
+    fn cells(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars()
+    }
+}
+
+impl CharRow for &str {
+    fn cells(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars()
+    }
+}
+
+pub fn transpose_aligned<R: CharRow>(rows: &[R], options: &TransposeOptions) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::new();
+
+    for (row_count, row) in rows.iter().enumerate() {
+        let mut width = 0;
+        for (index, ch) in row.cells().enumerate() {
+            if index == columns.len() {
+                // a new widest row, the rows before it didn't reach this far
+                columns.push(Column {
+                    index,
+                    cells: vec![options.fill; row_count],
+                });
             }
-        })
-        .collect()
-}
\ No newline at end of file
+            columns[index].cells.push(ch);
+            width = index + 1;
+        }
+
+        for column in &mut columns[width..] {
+            column.cells.push(options.fill);
+        }
+    }
+
+    if options.skip_whitespace {
+        for column in &mut columns {
+            column.cells.retain(|c| !c.is_whitespace());
+        }
+    }
+
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_strings(columns: &[Column]) -> Vec<String> {
+        columns.iter().map(|column| column.cells.iter().collect()).collect()
+    }
+
+    #[test]
+    fn pads_ragged_rows_with_fill() {
+        let options = TransposeOptions {
+            fill: '.',
+            ..Default::default()
+        };
+        let columns = transpose_aligned(&["ab", "abcd", "a"], &options);
+
+        assert_eq!(as_strings(&columns), vec!["aaa", "bb.", ".c.", ".d."]);
+        let indices: Vec<usize> = columns.iter().map(|column| column.index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn skip_whitespace_keeps_the_original_index() {
+        let options = TransposeOptions {
+            skip_whitespace: true,
+            ..Default::default()
+        };
+        let columns = transpose_aligned(&["a b", "  c", "d"], &options);
+
+        assert_eq!(
+            columns,
+            vec![
+                Column { index: 0, cells: vec!['a', 'd'] },
+                Column { index: 1, cells: vec![] },
+                Column { index: 2, cells: vec!['b', 'c'] },
+            ]
+        );
+    }
+
+    #[test]
+    fn every_kind_of_row_transposes_the_same() {
+        let options = TransposeOptions::default();
+        let strs = ["ab", "c"];
+        let strings: Vec<String> = strs.iter().map(|row| row.to_string()).collect();
+        let chars: Vec<Vec<char>> = strs.iter().map(|row| row.chars().collect()).collect();
+
+        let expected = transpose_aligned(&strs, &options);
+        assert_eq!(as_strings(&expected), vec!["ac", "b "]);
+        assert_eq!(transpose_aligned(&strings, &options), expected);
+        assert_eq!(transpose_aligned(&chars, &options), expected);
+    }
+
+    #[test]
+    fn no_rows_have_no_columns() {
+        let rows: [&str; 0] = [];
+        assert!(transpose_aligned(&rows, &TransposeOptions::default()).is_empty());
+    }
+}